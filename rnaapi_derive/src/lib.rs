@@ -1,175 +1,510 @@
 //use proc_macro::TokenStream;
 //use rnahelpers::EndpointGetArgs;
 use syn::DeriveInput;
+use syn::parse::ParseStream;
+use syn::punctuated::Punctuated;
+
+/// `params(mbpkgid: MbPkgId, job_id: JobId)`, filling the path
+/// placeholders, by name for `{mbpkgid}`, in the order given for `{}`.
+/// Also used for `query(...)`, URL encoded after the path, where `Option`s
+/// are left out when `None` and `#[serde(rename = "type")]` renames the
+/// query key
+#[derive(Default)]
+struct Params(Vec<syn::Field>);
+
+impl deluxe::ParseMetaItem for Params {
+    fn parse_meta_item(
+        input: ParseStream, _mode: deluxe::ParseMode,
+    ) -> deluxe::Result<Self> {
+        let content;
+        syn::parenthesized!(content in input);
+        let fields =
+            Punctuated::<syn::Field, syn::Token![,]>::parse_terminated_with(
+                &content,
+                syn::Field::parse_named,
+            )?;
+        Ok(Params(fields.into_iter().collect()))
+    }
+
+    fn parse_meta_item_named(
+        input: ParseStream, _name: &str, _span: proc_macro2::Span,
+    ) -> deluxe::Result<Self> {
+        Self::parse_meta_item(input, deluxe::ParseMode::Unnamed)
+    }
+}
 
 #[derive(deluxe::ExtractAttributes)]
 #[deluxe(attributes(getone))]
 struct EndpointGetOneAttrs {
-    path: String,
-    args: u32,
+    path: syn::LitStr,
+    #[deluxe(default)]
+    params: Params,
+    #[deluxe(default)]
+    query: Params,
 }
 
 #[derive(deluxe::ExtractAttributes)]
 #[deluxe(attributes(getall))]
 struct EndpointGetAllAttrs {
-    path: String,
-    args: u32,
+    path: syn::LitStr,
+    #[deluxe(default)]
+    params: Params,
+    #[deluxe(default)]
+    query: Params,
 }
 
-fn impl_endpointgetall_trait(
-    item: proc_macro2::TokenStream,
-) -> deluxe::Result<proc_macro2::TokenStream> {
-    // get struct identifier
-    let mut ast: DeriveInput = syn::parse2(item)?;
+#[derive(deluxe::ExtractAttributes)]
+#[deluxe(attributes(create))]
+struct EndpointCreateAttrs {
+    path: syn::LitStr,
+    #[deluxe(default)]
+    params: Params,
+    #[deluxe(default)]
+    query: Params,
+    request: syn::Type,
+    #[deluxe(default)]
+    json: bool,
+}
 
-    // get the attributes (required)
-    let EndpointGetAllAttrs { path, args } =
-        deluxe::extract_attributes(&mut ast)?;
-    let ident = ast.ident;
+#[derive(deluxe::ExtractAttributes)]
+#[deluxe(attributes(update))]
+struct EndpointUpdateAttrs {
+    path: syn::LitStr,
+    #[deluxe(default)]
+    params: Params,
+    #[deluxe(default)]
+    query: Params,
+    request: syn::Type,
+    #[deluxe(default)]
+    json: bool,
+}
 
-    if args == 0 {
-        Ok(quote::quote! {
-            #[async_trait]
-            impl EndpointGetAll for #ident {
-                type Endpoint = #ident;
-                async fn get_all(
-                    na_client: &NaClient, args: EndpointGetArgs
-                ) -> Result<Vec<#ident>, NaApiError> {
-                    match args {
-                        EndpointGetArgs::NoArgs => {
-                            let data = na_client.get_data(&format!(#path)).await?;
-                            let results: Vec<#ident> = serde_json::from_value(data).unwrap();
-                            Ok(results)
-                        }
-                        _ => Err(NaApiError::UnknownError(
-                                "No args Allowed".to_owned(),
-                        ))
-                    }
-                }
+#[derive(deluxe::ExtractAttributes)]
+#[deluxe(attributes(delete))]
+struct EndpointDeleteAttrs {
+    path: syn::LitStr,
+    #[deluxe(default)]
+    params: Params,
+    #[deluxe(default)]
+    query: Params,
+}
+
+/// Everything generated from the `params(...)` of one derive
+struct ParamsImpl {
+    /// the associated `Params` type
+    ty: proc_macro2::TokenStream,
+    /// the struct behind it, empty when there are no params
+    def: proc_macro2::TokenStream,
+    /// binding for the params argument of the trait method
+    arg: proc_macro2::TokenStream,
+    /// the formatted path
+    url: proc_macro2::TokenStream,
+}
+
+/// The placeholders in a path, `None` for `{}` and the name for `{name}`
+fn placeholders(path: &syn::LitStr) -> syn::Result<Vec<Option<String>>> {
+    let value = path.value();
+    let mut found = Vec::new();
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
             }
-        })
-    } else if args == 1 {
-        Ok(quote::quote! {
-            #[async_trait]
-            impl EndpointGetAll for #ident {
-                type Endpoint = #ident;
-                async fn get_all(
-                    na_client: &NaClient, args: EndpointGetArgs
-                ) -> Result<Vec<#ident>, NaApiError> {
-                    match args {
-                        EndpointGetArgs::OneInt(arg1) => {
-                            let data = na_client.get_data(&format!(#path, arg1)).await?;
-                            let results: Vec<#ident> = serde_json::from_value(data).unwrap();
-                            Ok(results)
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+            }
+            '{' => {
+                let mut inner = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => inner.push(c),
+                        None => {
+                            return Err(syn::Error::new_spanned(
+                                path,
+                                "unclosed `{` in path",
+                            ));
                         }
-                        _ => Err(NaApiError::UnknownError(
-                                "Only one arg Allowed".to_owned(),
-                        ))
                     }
                 }
+                // anything after a `:` is a format spec
+                let name = inner.split(':').next().unwrap_or("").trim();
+                if name.is_empty() {
+                    found.push(None);
+                } else if syn::parse_str::<syn::Ident>(name).is_ok() {
+                    found.push(Some(name.to_owned()));
+                } else {
+                    return Err(syn::Error::new_spanned(
+                        path,
+                        format!("`{{{inner}}}` is not a param placeholder"),
+                    ));
+                }
             }
-        })
+            '}' => {
+                return Err(syn::Error::new_spanned(
+                    path,
+                    "unmatched `}` in path, use `}}` for a literal one",
+                ));
+            }
+            _ => {}
+        }
+    }
+    Ok(found)
+}
+
+/// Make sure the path placeholders and the declared params line up,
+/// returns true when the placeholders are named
+fn check_params(
+    path: &syn::LitStr, fields: &[syn::Field],
+) -> syn::Result<bool> {
+    let found = placeholders(path)?;
+    let names: Vec<String> = fields
+        .iter()
+        .filter_map(|f| f.ident.as_ref().map(|i| i.to_string()))
+        .collect();
+    for (i, field) in fields.iter().enumerate() {
+        if names[..i].contains(&names[i]) {
+            return Err(syn::Error::new_spanned(
+                &field.ident,
+                format!("param `{}` is declared twice", names[i]),
+            ));
+        }
+    }
+    let positional = found.iter().filter(|p| p.is_none()).count();
+    if positional > 0 {
+        if positional != found.len() {
+            return Err(syn::Error::new_spanned(
+                path,
+                "path mixes `{}` and named placeholders, use one or the other",
+            ));
+        }
+        if positional != fields.len() {
+            return Err(syn::Error::new_spanned(
+                path,
+                format!(
+                    "path has {positional} `{{}}` placeholder(s) but {} \
+                     param(s) are declared",
+                    fields.len()
+                ),
+            ));
+        }
+        return Ok(false);
+    }
+    for name in found.iter().flatten() {
+        if !names.contains(name) {
+            let message = if names.is_empty() {
+                format!("`{{{name}}}` needs a param, IE `params({name}: ...)`")
+            } else {
+                format!(
+                    "`{{{name}}}` is not one of the params ({})",
+                    names.join(", ")
+                )
+            };
+            return Err(syn::Error::new_spanned(path, message));
+        }
+    }
+    for (field, name) in fields.iter().zip(&names) {
+        if !found.iter().flatten().any(|p| p == name) {
+            return Err(syn::Error::new_spanned(
+                &field.ident,
+                format!("param `{name}` has no `{{{name}}}` in the path"),
+            ));
+        }
+    }
+    Ok(true)
+}
+
+/// Params struct named `<Struct><Trait>Params`, IE `SrvJobGetOneParams`
+fn params_impl(
+    ast: &DeriveInput, method: &str, path: &syn::LitStr, params: Params,
+    query: Params,
+) -> syn::Result<ParamsImpl> {
+    let Params(fields) = params;
+    let Params(query) = query;
+    let named = check_params(path, &fields)?;
+    for field in &query {
+        if fields.iter().any(|f| f.ident == field.ident) {
+            return Err(syn::Error::new_spanned(
+                &field.ident,
+                "already declared as a path param",
+            ));
+        }
+    }
+    if fields.is_empty() && query.is_empty() {
+        return Ok(ParamsImpl {
+            ty: quote::quote! { () },
+            def: quote::quote! {},
+            arg: quote::quote! { _params },
+            url: quote::quote! { format!(#path) },
+        });
+    }
+    let ident = &ast.ident;
+    let vis = &ast.vis;
+    let name = quote::format_ident!("{}{}Params", ident, method);
+    let doc = format!("Parameters for `{ident}::{}`", method_name(method));
+    let names: Vec<_> = fields.iter().map(|f| &f.ident).collect();
+    let types: Vec<_> = fields.iter().map(|f| &f.ty).collect();
+    let attrs: Vec<_> = fields.iter().map(|f| &f.attrs).collect();
+    let path_url = if named {
+        quote::quote! { format!(#path, #(#names = params.#names),*) }
     } else {
-        Ok(quote::quote! {
-            #[async_trait]
-            impl EndpointGetAll for #ident {
-                type Endpoint = #ident;
-                async fn get_all(
-                    na_client: &NaClient, args: EndpointGetArgs
-                ) -> Result<Vec<#ident>, NaApiError> {
-                    match args {
-                        EndpointGetArgs::TwoInt(arg1, arg2) => {
-                            let data = na_client.get_data(&format!(#path, arg1, arg2)).await?;
-                            let results: Vec<#ident> = serde_json::from_value(data).unwrap();
-                            Ok(results)
-                        }
-                        _ => Err(NaApiError::UnknownError(
-                                "Only two args Allowed".to_owned(),
-                        ))
-                    }
+        quote::quote! { format!(#path, #(params.#names),*) }
+    };
+    if query.is_empty() {
+        return Ok(ParamsImpl {
+            ty: quote::quote! { #name },
+            def: quote::quote! {
+                #[doc = #doc]
+                #[derive(Clone, Debug, PartialEq)]
+                #vis struct #name {
+                    #( #(#attrs)* pub #names: #types, )*
                 }
+            },
+            arg: quote::quote! { params },
+            url: path_url,
+        });
+    }
+
+    // the path params are not part of the query string
+    let query_names: Vec<_> = query.iter().map(|f| &f.ident).collect();
+    let query_types: Vec<_> = query.iter().map(|f| &f.ty).collect();
+    let query_attrs: Vec<_> = query.iter().map(|f| &f.attrs).collect();
+    // without path params, and nothing required, no params is a default
+    let default = if fields.is_empty() && query.iter().all(|f| is_option(&f.ty))
+    {
+        quote::quote! { Default, }
+    } else {
+        quote::quote! {}
+    };
+    Ok(ParamsImpl {
+        ty: quote::quote! { #name },
+        def: quote::quote! {
+            #[doc = #doc]
+            #[derive(Clone, Debug, #default PartialEq, Serialize)]
+            #vis struct #name {
+                #( #(#attrs)* #[serde(skip)] pub #names: #types, )*
+                #( #(#query_attrs)* pub #query_names: #query_types, )*
             }
-        })
+        },
+        arg: quote::quote! { params },
+        url: quote::quote! { NaClient::with_query(&#path_url, &params)? },
+    })
+}
+
+/// `Option<T>`, however it is spelled
+fn is_option(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(ty) => ty
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
     }
 }
 
-fn impl_endpointgetone_trait(
+/// `GetOne` to `get_one`
+fn method_name(method: &str) -> String {
+    let mut name = String::new();
+    for c in method.chars() {
+        if c.is_uppercase() && !name.is_empty() {
+            name.push('_');
+        }
+        name.push(c.to_ascii_lowercase());
+    }
+    name
+}
+
+/// Request body constructor, form encoded unless json was asked for
+fn request_body(json: bool) -> proc_macro2::TokenStream {
+    if json {
+        quote::quote! { RequestBody::json(request)? }
+    } else {
+        quote::quote! { RequestBody::form(request)? }
+    }
+}
+
+fn impl_endpointcreate_trait(
     item: proc_macro2::TokenStream,
 ) -> deluxe::Result<proc_macro2::TokenStream> {
     // get struct identifier
     let mut ast: DeriveInput = syn::parse2(item)?;
 
     // get the attributes (required)
-    let EndpointGetOneAttrs { path, args } =
-        deluxe::extract_attributes(&mut ast)?;
+    let EndpointCreateAttrs {
+        path,
+        params,
+        query,
+        request,
+        json,
+    } = deluxe::extract_attributes(&mut ast)?;
+    let ParamsImpl { ty, def, arg, url } =
+        params_impl(&ast, "Create", &path, params, query)?;
     let ident = ast.ident;
+    let body = request_body(json);
 
-    // generate impl
+    Ok(quote::quote! {
+        #def
 
-    if args == 0 {
-        Ok(quote::quote! {
-            #[async_trait]
-            impl EndpointGetOne for #ident {
-                type Endpoint = #ident;
-                async fn get_one(
-                    na_client: &NaClient, args: EndpointGetArgs
-                ) -> Result<#ident, NaApiError> {
-                    match args {
-                        EndpointGetArgs::NoArgs => {
-                            let data = na_client.get_data(&format!(#path)).await?;
-                            let result: #ident = serde_json::from_value(data).unwrap();
-                            Ok(result)
-                        }
-                        _ => Err(NaApiError::UnknownError(
-                                "No args Allowed".to_owned(),
-                        ))
-                    }
-                }
+        #[async_trait]
+        impl EndpointCreate for #ident {
+            type Endpoint = #ident;
+            type Params = #ty;
+            type Request = #request;
+            async fn create(
+                na_client: &NaClient, #arg: #ty, request: &#request
+            ) -> Result<#ident, NaApiError> {
+                let path = #url;
+                na_client.post_as(stringify!(#ident), &path, #body).await
             }
-        })
-    } else if args == 1 {
-        Ok(quote::quote! {
-            #[async_trait]
-            impl EndpointGetOne for #ident {
-                type Endpoint = #ident;
-                async fn get_one(
-                    na_client: &NaClient, args: EndpointGetArgs
-                ) -> Result<#ident, NaApiError> {
-                    match args {
-                        EndpointGetArgs::OneInt(arg1) => {
-                            let data = na_client.get_data(&format!(#path, arg1)).await?;
-                            let result: #ident = serde_json::from_value(data).unwrap();
-                            Ok(result)
-                        }
-                        _ => Err(NaApiError::UnknownError(
-                                "Only one arg Allowed".to_owned(),
-                        ))
-                    }
-                }
+        }
+    })
+}
+
+fn impl_endpointupdate_trait(
+    item: proc_macro2::TokenStream,
+) -> deluxe::Result<proc_macro2::TokenStream> {
+    // get struct identifier
+    let mut ast: DeriveInput = syn::parse2(item)?;
+
+    // get the attributes (required)
+    let EndpointUpdateAttrs {
+        path,
+        params,
+        query,
+        request,
+        json,
+    } = deluxe::extract_attributes(&mut ast)?;
+    let ParamsImpl { ty, def, arg, url } =
+        params_impl(&ast, "Update", &path, params, query)?;
+    let ident = ast.ident;
+    let body = request_body(json);
+
+    Ok(quote::quote! {
+        #def
+
+        #[async_trait]
+        impl EndpointUpdate for #ident {
+            type Endpoint = #ident;
+            type Params = #ty;
+            type Request = #request;
+            async fn update(
+                na_client: &NaClient, #arg: #ty, request: &#request
+            ) -> Result<#ident, NaApiError> {
+                let path = #url;
+                na_client.put_as(stringify!(#ident), &path, #body).await
             }
-        })
-    } else {
-        Ok(quote::quote! {
-            #[async_trait]
-            impl EndpointGetOne for #ident {
-                type Endpoint = #ident;
-                async fn get_one(
-                    na_client: &NaClient, args: EndpointGetArgs
-                ) -> Result<#ident, NaApiError> {
-                    match args {
-                        EndpointGetArgs::TwoInt(arg1, arg2) => {
-                            let data = na_client.get_data(&format!(#path, arg1, arg2)).await?;
-                            let result: #ident = serde_json::from_value(data).unwrap();
-                            Ok(result)
-                        }
-                        _ => Err(NaApiError::UnknownError(
-                                "Only two args Allowed".to_owned(),
-                        ))
-                    }
-                }
+        }
+    })
+}
+
+fn impl_endpointdelete_trait(
+    item: proc_macro2::TokenStream,
+) -> deluxe::Result<proc_macro2::TokenStream> {
+    // get struct identifier
+    let mut ast: DeriveInput = syn::parse2(item)?;
+
+    // get the attributes (required)
+    let EndpointDeleteAttrs {
+        path,
+        params,
+        query,
+    } = deluxe::extract_attributes(&mut ast)?;
+    let ParamsImpl { ty, def, arg, url } =
+        params_impl(&ast, "Delete", &path, params, query)?;
+    let ident = ast.ident;
+
+    Ok(quote::quote! {
+        #def
+
+        #[async_trait]
+        impl EndpointDelete for #ident {
+            type Params = #ty;
+            async fn delete(
+                na_client: &NaClient, #arg: #ty
+            ) -> Result<(), NaApiError> {
+                na_client.delete_data(&#url, RequestBody::Empty).await?;
+                Ok(())
             }
-        })
-    }
+        }
+    })
+}
+
+fn impl_endpointgetall_trait(
+    item: proc_macro2::TokenStream,
+) -> deluxe::Result<proc_macro2::TokenStream> {
+    // get struct identifier
+    let mut ast: DeriveInput = syn::parse2(item)?;
+
+    // get the attributes (required)
+    let EndpointGetAllAttrs {
+        path,
+        params,
+        query,
+    } = deluxe::extract_attributes(&mut ast)?;
+    let ParamsImpl { ty, def, arg, url } =
+        params_impl(&ast, "GetAll", &path, params, query)?;
+    let ident = ast.ident;
+
+    Ok(quote::quote! {
+        #def
+
+        #[async_trait]
+        impl EndpointGetAll for #ident {
+            type Endpoint = #ident;
+            type Params = #ty;
+            async fn get_all(
+                na_client: &NaClient, #arg: #ty
+            ) -> Result<Vec<#ident>, NaApiError> {
+                let path = #url;
+                na_client.get_all_as(stringify!(#ident), &path).await
+            }
+
+            fn stream_all(
+                na_client: &NaClient, #arg: #ty
+            ) -> ItemStream<#ident> {
+                na_client.stream_as(stringify!(#ident), || {
+                    let path = #url;
+                    Ok(path)
+                })
+            }
+        }
+    })
+}
+
+fn impl_endpointgetone_trait(
+    item: proc_macro2::TokenStream,
+) -> deluxe::Result<proc_macro2::TokenStream> {
+    // get struct identifier
+    let mut ast: DeriveInput = syn::parse2(item)?;
+
+    // get the attributes (required)
+    let EndpointGetOneAttrs {
+        path,
+        params,
+        query,
+    } = deluxe::extract_attributes(&mut ast)?;
+    let ParamsImpl { ty, def, arg, url } =
+        params_impl(&ast, "GetOne", &path, params, query)?;
+    let ident = ast.ident;
+
+    // generate impl
+    Ok(quote::quote! {
+        #def
+
+        #[async_trait]
+        impl EndpointGetOne for #ident {
+            type Endpoint = #ident;
+            type Params = #ty;
+            async fn get_one(
+                na_client: &NaClient, #arg: #ty
+            ) -> Result<#ident, NaApiError> {
+                let path = #url;
+                na_client.get_as(stringify!(#ident), &path).await
+            }
+        }
+    })
 }
 
 #[proc_macro_derive(EndpointGetOne, attributes(getone))]
@@ -177,7 +512,9 @@ pub fn endpointgetone_derive_macro(
     item: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
     // generate
-    impl_endpointgetone_trait(item.into()).unwrap().into()
+    impl_endpointgetone_trait(item.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
 
 #[proc_macro_derive(EndpointGetAll, attributes(getall))]
@@ -185,5 +522,37 @@ pub fn endpointgetall_derive_macro(
     item: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
     // generate
-    impl_endpointgetall_trait(item.into()).unwrap().into()
+    impl_endpointgetall_trait(item.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(EndpointCreate, attributes(create))]
+pub fn endpointcreate_derive_macro(
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    // generate
+    impl_endpointcreate_trait(item.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(EndpointUpdate, attributes(update))]
+pub fn endpointupdate_derive_macro(
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    // generate
+    impl_endpointupdate_trait(item.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(EndpointDelete, attributes(delete))]
+pub fn endpointdelete_derive_macro(
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    // generate
+    impl_endpointdelete_trait(item.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
@@ -0,0 +1,68 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! Decoding a large list response, through a `Value` the way responses
+//! used to be handled against the typed `Envelope` straight from bytes
+//!
+//! Run with `cargo bench -p rnaapi --bench decode`
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use rnaapi::endpoints::{Image, Invoices, Record};
+use rnaapi::envelope::Envelope;
+use rnaapi::fixtures::Fixture;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use std::hint::black_box;
+use std::path::PathBuf;
+
+const ITEMS: usize = 5_000;
+
+/// A recorded list response blown up to `ITEMS` entries
+fn body(fixture: &str) -> Vec<u8> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(fixture);
+    let body = Fixture::load(&path).expect("fixture").body.expect("json");
+    let items = body["data"].as_array().expect("a list");
+    let data: Vec<&Value> = items.iter().cycle().take(ITEMS).collect();
+    serde_json::to_vec(&json!({"code": 200, "data": data})).expect("encode")
+}
+
+/// Parse to a `Value`, clone out "data", then decode that, tracking the
+/// path of a mismatch the same way the envelope does
+fn via_value<T: DeserializeOwned>(body: &[u8]) -> Vec<T> {
+    let value: Value = serde_json::from_slice(body).expect("json");
+    let data = value.get("data").expect("data").clone();
+    serde_path_to_error::deserialize(data).expect("decode")
+}
+
+/// Decode the typed envelope in one pass
+fn via_envelope<T: DeserializeOwned>(body: &[u8]) -> Vec<T> {
+    let envelope: Envelope<Vec<T>> =
+        Envelope::from_slice("bench", "bench", body).expect("decode");
+    envelope.data.expect("data")
+}
+
+fn compare<T: DeserializeOwned>(c: &mut Criterion, name: &str, fixture: &str) {
+    let body = body(fixture);
+    let mut group = c.benchmark_group(name);
+    group.bench_with_input(
+        BenchmarkId::new("value", ITEMS),
+        &body,
+        |b, body| b.iter(|| via_value::<T>(black_box(body))),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("envelope", ITEMS),
+        &body,
+        |b, body| b.iter(|| via_envelope::<T>(black_box(body))),
+    );
+    group.finish();
+}
+
+fn decode(c: &mut Criterion) {
+    compare::<Invoices>(c, "invoices", "get_account_invoices.json");
+    compare::<Record>(c, "dns_records", "get_dns_records_801.json");
+    compare::<Image>(c, "images", "get_cloud_images.json");
+}
+
+criterion_group!(benches, decode);
+criterion_main!(benches);
@@ -0,0 +1,92 @@
+//! Local NetActuate API simulator
+//!
+//! ```bash
+//! cargo install --path rnaapi_lib --features sim --bin rnasim
+//! rnasim --listen 127.0.0.1:8080 --seed fixtures/sim/seed.json
+//! export API_ADDRESS='http://127.0.0.1:8080/api/'
+//! export API_KEY='sim-api-key'
+//! rnatui get server
+//! ```
+//!
+//! With `--stateful` jobs take (virtual) time, `--speed 0` stops the clock
+//! so it only moves on `POST /api/sim/advance?seconds=N`
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+use anyhow::Result;
+use clap::Parser;
+use rnaapi::sim::{
+    DEFAULT_API_KEY, DEFAULT_PAGE_SIZE, JobTiming, SimState, Simulator,
+};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::net::TcpListener;
+
+///
+/// Serve a fake NetActuate API from fixture files
+///
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Cli {
+    /// address to listen on
+    #[arg(short, long, default_value = "127.0.0.1:8080")]
+    listen: SocketAddr,
+
+    /// seed file or directory, the shipped seed when not given
+    #[arg(short, long)]
+    seed: Option<PathBuf>,
+
+    /// API key clients have to send
+    #[arg(short = 'k', long, default_value = DEFAULT_API_KEY)]
+    api_key: String,
+
+    /// run jobs through queued and running instead of finishing at once
+    #[arg(long)]
+    stateful: bool,
+
+    /// virtual seconds per real second, 0 only moves on `sim/advance`
+    #[arg(long, default_value_t = 1.0)]
+    speed: f64,
+
+    /// virtual seconds a job stays queued
+    #[arg(long, default_value_t = 5)]
+    queued_secs: u64,
+
+    /// virtual seconds a boot, shutdown or reboot runs
+    #[arg(long, default_value_t = 30)]
+    running_secs: u64,
+
+    /// virtual seconds a build or rebuild runs
+    #[arg(long, default_value_t = 180)]
+    building_secs: u64,
+
+    /// invoices and DNS records past this many are sent in pages
+    #[arg(long, default_value_t = DEFAULT_PAGE_SIZE)]
+    page_size: usize,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let state = match &cli.seed {
+        Some(path) => SimState::load(path)?,
+        None => SimState::default_seed(),
+    };
+    let listener = TcpListener::bind(cli.listen).await?;
+    println!("rnasim serving on http://{}/api/", listener.local_addr()?);
+    let mut sim = Simulator::new(state)
+        .with_api_key(cli.api_key)
+        .with_page_size(cli.page_size);
+    if cli.stateful {
+        sim = sim
+            .stateful(JobTiming {
+                queued: Duration::from_secs(cli.queued_secs),
+                running: Duration::from_secs(cli.running_secs),
+                building: Duration::from_secs(cli.building_secs),
+            })
+            .with_clock_speed(cli.speed);
+    }
+    sim.serve(listener).await?;
+    Ok(())
+}
@@ -0,0 +1,287 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+use reqwest::{Certificate, ClientBuilder, Proxy};
+use reqwest_hickory_resolver::HickoryResolver;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::drift::{DriftTracker, SchemaDrift};
+use crate::errors::NaApiError;
+use crate::fixtures::RecordingTransport;
+use crate::limit::{RateLimit, Throttle};
+use crate::retry::RetryPolicy;
+use crate::secret::ApiKey;
+use crate::transport::{ReqwestTransport, Transport};
+use crate::{KeyPlacement, NaClient};
+
+/// Default User-Agent, `rnaapi/<version>`
+pub const USER_AGENT: &str = concat!("rnaapi/", env!("CARGO_PKG_VERSION"));
+
+/// How host names are turned into addresses
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Resolver {
+    /// hickory, IE Ipv4 first, what `NaClient::new` has always used
+    #[default]
+    Hickory,
+    /// whatever the operating system resolver says
+    System,
+    /// fixed addresses per host name, anything not listed goes to the
+    /// system resolver
+    Fixed(BTreeMap<String, Vec<SocketAddr>>),
+}
+
+/// Builder for a `NaClient` with control over the http client underneath
+///
+/// ```rust,no_run
+/// use rnaapi::{NaClient, Resolver};
+/// use std::time::Duration;
+///
+/// # fn main() -> Result<(), rnaapi::errors::NaApiError> {
+/// let client = NaClient::builder("key", "https://vapi2.netactuate.com/api/")
+///     .connect_timeout(Duration::from_secs(5))
+///     .timeout(Duration::from_secs(30))
+///     .proxy("http://proxy.internal:3128")
+///     .resolver(Resolver::System)
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct NaClientBuilder {
+    api_key: ApiKey,
+    address: String,
+    key_placement: KeyPlacement,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    proxy: Option<String>,
+    user_agent: String,
+    ca_pem: Vec<Vec<u8>>,
+    ca_files: Vec<PathBuf>,
+    resolver: Resolver,
+    retry: RetryPolicy,
+    rate_limit: Option<RateLimit>,
+    max_concurrency: Option<usize>,
+    transport: Option<Arc<dyn Transport>>,
+    record: Option<PathBuf>,
+    schema_drift: SchemaDrift,
+}
+
+impl fmt::Debug for NaClientBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NaClientBuilder")
+            .field("api_key", &self.api_key)
+            .field("address", &self.address)
+            .field("key_placement", &self.key_placement)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("timeout", &self.timeout)
+            .field(
+                "proxy",
+                &self.proxy.as_deref().map(|p| self.api_key.redact(p)),
+            )
+            .field("user_agent", &self.user_agent)
+            .field("ca_files", &self.ca_files)
+            .field("resolver", &self.resolver)
+            .field("retry", &self.retry)
+            .field("rate_limit", &self.rate_limit)
+            .field("max_concurrency", &self.max_concurrency)
+            .field("transport", &self.transport)
+            .field("record", &self.record)
+            .field("schema_drift", &self.schema_drift)
+            .finish_non_exhaustive()
+    }
+}
+
+impl NaClientBuilder {
+    pub fn new(api_key: impl Into<ApiKey>, address: impl Into<String>) -> Self {
+        NaClientBuilder {
+            api_key: api_key.into(),
+            address: address.into(),
+            key_placement: KeyPlacement::default(),
+            connect_timeout: None,
+            timeout: None,
+            proxy: None,
+            user_agent: USER_AGENT.to_owned(),
+            ca_pem: Vec::new(),
+            ca_files: Vec::new(),
+            resolver: Resolver::default(),
+            retry: RetryPolicy::default(),
+            rate_limit: None,
+            max_concurrency: None,
+            transport: None,
+            record: None,
+            schema_drift: SchemaDrift::default(),
+        }
+    }
+
+    /// Where the API key goes on each request, see `KeyPlacement`
+    pub fn key_placement(mut self, key_placement: KeyPlacement) -> Self {
+        self.key_placement = key_placement;
+        self
+    }
+
+    /// Time allowed to establish the connection
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Time allowed for the whole request, connect through to the body
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Send everything through an http(s) proxy, IE `http://proxy:3128`
+    pub fn proxy(mut self, url: impl Into<String>) -> Self {
+        self.proxy = Some(url.into());
+        self
+    }
+
+    /// Replace the default `rnaapi/<version>` User-Agent
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Trust the certificates in a PEM bundle on top of the system ones
+    pub fn ca_bundle_pem(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.ca_pem.push(pem.into());
+        self
+    }
+
+    /// Same as `ca_bundle_pem`, read from a file when building
+    pub fn ca_bundle_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.ca_files.push(path.into());
+        self
+    }
+
+    /// Pick how host names get resolved
+    pub fn resolver(mut self, resolver: Resolver) -> Self {
+        self.resolver = resolver;
+        self
+    }
+
+    /// Retry transient failures, see `RetryPolicy`
+    pub fn retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Keep under the account's allowed request rate, see `RateLimit`
+    pub fn rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    /// Never have more than `max` requests in flight at once
+    pub fn max_concurrency(mut self, max: usize) -> Self {
+        self.max_concurrency = Some(max);
+        self
+    }
+
+    /// Execute requests with something other than reqwest, IE a
+    /// `MockTransport` in tests. The http settings above are then unused
+    pub fn transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Write every response to a fixture file in `dir`, key scrubbed,
+    /// see `fixtures::RecordingTransport`
+    pub fn record_fixtures(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.record = Some(dir.into());
+        self
+    }
+
+    /// Compare responses with their structs and log or collect the
+    /// differences, see `drift`
+    pub fn schema_drift(mut self, schema_drift: SchemaDrift) -> Self {
+        self.schema_drift = schema_drift;
+        self
+    }
+
+    /// Build the http client and wrap it up in a `NaClient`
+    pub fn build(mut self) -> Result<NaClient, NaApiError> {
+        let mut transport = match self.transport.take() {
+            Some(transport) => transport,
+            None => Arc::new(ReqwestTransport::new(self.http_client()?)),
+        };
+        if let Some(dir) = self.record.take() {
+            transport = Arc::new(RecordingTransport::new(
+                transport,
+                dir,
+                self.api_key.clone(),
+            ));
+        }
+        Ok(NaClient {
+            address: self.address,
+            api_key: self.api_key,
+            key_placement: self.key_placement,
+            retry: self.retry,
+            throttle: Arc::new(Throttle::new(
+                self.rate_limit,
+                self.max_concurrency,
+            )),
+            transport,
+            drift: Arc::new(DriftTracker::new(self.schema_drift)),
+        })
+    }
+
+    /// The reqwest client for the default transport
+    fn http_client(&self) -> Result<reqwest::Client, NaApiError> {
+        let mut builder =
+            ClientBuilder::new().user_agent(self.user_agent.as_str());
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(proxy) = &self.proxy {
+            let proxy = Proxy::all(proxy.as_str()).map_err(|e| {
+                NaApiError::UnknownError(format!("Invalid proxy: {e}"))
+            })?;
+            builder = builder.proxy(proxy);
+        }
+
+        let mut bundles = self.ca_pem.clone();
+        for path in &self.ca_files {
+            let pem = std::fs::read(path).map_err(|e| {
+                NaApiError::UnknownError(format!(
+                    "Could not read CA bundle {}: {e}",
+                    path.display()
+                ))
+            })?;
+            bundles.push(pem);
+        }
+        for pem in &bundles {
+            let certs = Certificate::from_pem_bundle(pem).map_err(|e| {
+                NaApiError::UnknownError(format!("Invalid CA bundle: {e}"))
+            })?;
+            for cert in certs {
+                builder = builder.add_root_certificate(cert);
+            }
+        }
+
+        builder = match &self.resolver {
+            Resolver::Hickory => {
+                builder.dns_resolver(Arc::new(HickoryResolver::default()))
+            }
+            Resolver::System => builder,
+            Resolver::Fixed(hosts) => {
+                for (host, addrs) in hosts {
+                    builder = builder.resolve_to_addrs(host, addrs);
+                }
+                builder
+            }
+        };
+
+        builder.build().map_err(|e| {
+            NaApiError::UnknownError(format!("Error building client: {e}"))
+        })
+    }
+}
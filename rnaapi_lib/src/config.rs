@@ -6,10 +6,11 @@ use serde::Deserialize;
 use std::env as std_env;
 
 use crate::errors::NaApiError;
+use crate::secret::ApiKey;
 
 #[derive(Debug, Deserialize)]
 pub struct Settings {
-    pub api_key: String,
+    pub api_key: ApiKey,
     pub api_url: String,
 }
 
@@ -20,12 +21,21 @@ impl Settings {
     pub fn new() -> Result<Settings, NaApiError> {
         Ok(Settings {
             api_key: set_key()?,
-            api_url: Self::API_ADDRESS.to_string(),
+            api_url: set_address(),
         })
     }
 }
 
-fn set_key() -> Result<String, NaApiError> {
+// API_ADDRESS from ENV, IE a local simulator, or the real API
+fn set_address() -> String {
+    dotenv().ok();
+    match std_env::var("API_ADDRESS") {
+        Ok(address) if !address.is_empty() => address,
+        _ => Settings::API_ADDRESS.to_string(),
+    }
+}
+
+fn set_key() -> Result<ApiKey, NaApiError> {
     dotenv().ok();
     let apikey = match std_env::var("API_KEY") {
         Ok(key) => {
@@ -34,7 +44,7 @@ fn set_key() -> Result<String, NaApiError> {
                     "API_KEY is set but empty!".to_string(),
                 ));
             }
-            Ok(key)
+            Ok(ApiKey::new(key))
         }
         Err(_) => {
             return Err(NaApiError::APIKeyInvalid(
@@ -0,0 +1,136 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! Serde helpers for the date formats the API sends
+//!
+//! Timestamps come as `2025-09-20 14:02:11`, dates as `2025-09-20` and
+//! bandwidth months as `2025-09`. Unset values show up as `""`, `null` or
+//! MySQL style zero dates (`0000-00-00 00:00:00`), those all become
+//! `None`. Anything else that does not parse is an error
+//!
+//! ```rust
+//! use chrono::NaiveDateTime;
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Paid {
+//!     #[serde(with = "rnaapi::dates::datetime")]
+//!     datepaid: Option<NaiveDateTime>,
+//! }
+//!
+//! let paid: Paid =
+//!     serde_json::from_str(r#"{"datepaid": "0000-00-00 00:00:00"}"#).unwrap();
+//! assert_eq!(paid.datepaid, None);
+//! ```
+use chrono::{NaiveDate, NaiveDateTime, ParseResult};
+
+/// `YYYY-MM-DD HH:MM:SS`, timestamps
+pub const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+/// `YYYY-MM-DD`, plain dates
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+/// `YYYY-MM`, the month a bandwidth total is for
+pub const MONTH_FORMAT: &str = "%Y-%m";
+
+/// Empty and zero dates stand for "not set"
+fn is_unset(text: &str) -> bool {
+    let text = text.trim();
+    text.is_empty() || text.chars().all(|c| matches!(c, '0' | '-' | ':' | ' '))
+}
+
+/// Parse a timestamp, `None` for an unset one
+pub fn parse_datetime(text: &str) -> ParseResult<Option<NaiveDateTime>> {
+    if is_unset(text) {
+        return Ok(None);
+    }
+    NaiveDateTime::parse_from_str(text.trim(), DATETIME_FORMAT).map(Some)
+}
+
+/// Parse a date, `None` for an unset one
+pub fn parse_date(text: &str) -> ParseResult<Option<NaiveDate>> {
+    if is_unset(text) {
+        return Ok(None);
+    }
+    NaiveDate::parse_from_str(text.trim(), DATE_FORMAT).map(Some)
+}
+
+/// Parse a `YYYY-MM` month to its first day, a full date is taken too
+pub fn parse_month(text: &str) -> ParseResult<NaiveDate> {
+    let text = text.trim();
+    NaiveDate::parse_from_str(&format!("{text}-01"), DATE_FORMAT)
+        .or_else(|_| NaiveDate::parse_from_str(text, DATE_FORMAT))
+}
+
+/// `Option<NaiveDateTime>` as `YYYY-MM-DD HH:MM:SS`, unset is `None`
+pub mod datetime {
+    use chrono::NaiveDateTime;
+    use serde::{Deserialize, Deserializer, Serializer, de};
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<NaiveDateTime>, serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => {
+                serializer.collect_str(&value.format(super::DATETIME_FORMAT))
+            }
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<NaiveDateTime>, D::Error> {
+        let text = Option::<String>::deserialize(deserializer)?;
+        let text = text.as_deref().unwrap_or_default();
+        super::parse_datetime(text).map_err(|e| {
+            de::Error::custom(format!("invalid timestamp {text:?}: {e}"))
+        })
+    }
+}
+
+/// `Option<NaiveDate>` as `YYYY-MM-DD`, unset is `None`
+pub mod date {
+    use chrono::NaiveDate;
+    use serde::{Deserialize, Deserializer, Serializer, de};
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<NaiveDate>, serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => {
+                serializer.collect_str(&value.format(super::DATE_FORMAT))
+            }
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<NaiveDate>, D::Error> {
+        let text = Option::<String>::deserialize(deserializer)?;
+        let text = text.as_deref().unwrap_or_default();
+        super::parse_date(text).map_err(|e| {
+            de::Error::custom(format!("invalid date {text:?}: {e}"))
+        })
+    }
+}
+
+/// `NaiveDate` as `YYYY-MM`, the first day of the month
+pub mod month {
+    use chrono::NaiveDate;
+    use serde::{Deserialize, Deserializer, Serializer, de};
+
+    pub fn serialize<S: Serializer>(
+        value: &NaiveDate, serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&value.format(super::MONTH_FORMAT))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<NaiveDate, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        super::parse_month(&text).map_err(|e| {
+            de::Error::custom(format!("invalid month {text:?}: {e}"))
+        })
+    }
+}
@@ -0,0 +1,206 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! Noticing when the API and the endpoint structs drift apart
+//!
+//! Fields an endpoint struct does not know are dropped while decoding,
+//! which keeps it a single pass over the body. To keep them, decode into
+//! `WithExtra`, IE `client.get_all_as::<WithExtra<Image>>(..)`, they end
+//! up in its `extra` map. `extra` is never serialized, so it is not sent
+//! back to the API either. With `SchemaDrift::Log` or
+//! `SchemaDrift::Collect` set on the client, each response is also
+//! compared with what it was decoded into: fields the API sent that the
+//! struct has no place for are `unknown`, fields the struct has that the
+//! API left out are `missing`. Both are kept per endpoint, as JSON
+//! pointers into a single item
+//!
+//! ```rust
+//! use rnaapi::drift::Drift;
+//! use serde_json::json;
+//!
+//! let sent = json!({"id": 1, "tags": ["a"]});
+//! let kept = json!({"id": 1, "name": null});
+//! let drift = Drift::between(&sent, &kept);
+//! assert!(drift.unknown.contains("/tags"));
+//! assert!(drift.missing.contains("/name"));
+//! ```
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::{Deref, DerefMut};
+use std::sync::Mutex;
+
+/// What the client does about responses that don't match their struct
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SchemaDrift {
+    /// don't compare, what `NaClient::new` has always done
+    #[default]
+    Ignore,
+    /// collect, and log a warning the first time a field shows up
+    Log,
+    /// collect quietly, see `NaClient::drift_report`
+    Collect,
+}
+
+/// An endpoint `item` with the fields its struct has no place for.
+/// Those go through serde's buffered flatten path, so this is slower to
+/// decode than `T` on its own
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WithExtra<T> {
+    #[serde(flatten)]
+    pub item: T,
+    #[serde(flatten, skip_serializing)]
+    pub extra: BTreeMap<String, Value>,
+}
+
+impl<T> Deref for WithExtra<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.item
+    }
+}
+
+impl<T> DerefMut for WithExtra<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.item
+    }
+}
+
+/// Fields that differ between a response and its struct
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Drift {
+    /// sent by the API, not in the struct
+    pub unknown: BTreeSet<String>,
+    /// in the struct, not sent by the API
+    pub missing: BTreeSet<String>,
+}
+
+/// Drift seen so far, by endpoint, IE `Server`
+pub type DriftReport = BTreeMap<String, Drift>;
+
+impl Drift {
+    /// Compare the JSON that was `sent` with the struct it was decoded
+    /// into, serialized back as `kept`. A list on both sides is compared
+    /// item by item, items in nested lists show up as `*`
+    pub fn between(sent: &Value, kept: &Value) -> Drift {
+        let mut drift = Drift::default();
+        match (sent, kept) {
+            (Value::Array(sent), Value::Array(kept)) => {
+                for (sent, kept) in sent.iter().zip(kept) {
+                    drift.walk(sent, kept, "");
+                }
+            }
+            (sent, kept) => drift.walk(sent, kept, ""),
+        }
+        drift
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.unknown.is_empty() && self.missing.is_empty()
+    }
+
+    fn walk(&mut self, sent: &Value, kept: &Value, at: &str) {
+        match (sent, kept) {
+            (Value::Object(sent), Value::Object(kept)) => {
+                for (key, value) in sent {
+                    let at = pointer(at, key);
+                    match kept.get(key) {
+                        Some(kept) => self.walk(value, kept, &at),
+                        None => {
+                            self.unknown.insert(at);
+                        }
+                    }
+                }
+                for key in kept.keys().filter(|key| !sent.contains_key(*key)) {
+                    self.missing.insert(pointer(at, key));
+                }
+            }
+            (Value::Array(sent), Value::Array(kept)) => {
+                let at = format!("{at}/*");
+                for (sent, kept) in sent.iter().zip(kept) {
+                    self.walk(sent, kept, &at);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Add `other` in, handing back only what was not seen before
+    fn merge(&mut self, other: Drift) -> Drift {
+        Drift {
+            unknown: other
+                .unknown
+                .into_iter()
+                .filter(|field| self.unknown.insert(field.clone()))
+                .collect(),
+            missing: other
+                .missing
+                .into_iter()
+                .filter(|field| self.missing.insert(field.clone()))
+                .collect(),
+        }
+    }
+}
+
+fn pointer(at: &str, key: &str) -> String {
+    format!("{at}/{}", key.replace('~', "~0").replace('/', "~1"))
+}
+
+/// Compares responses as the client's `SchemaDrift` says, shared by
+/// the clones of a client
+#[derive(Debug, Default)]
+pub(crate) struct DriftTracker {
+    mode: SchemaDrift,
+    seen: Mutex<DriftReport>,
+}
+
+impl DriftTracker {
+    pub(crate) fn new(mode: SchemaDrift) -> Self {
+        DriftTracker {
+            mode,
+            seen: Mutex::default(),
+        }
+    }
+
+    pub(crate) fn mode(&self) -> SchemaDrift {
+        self.mode
+    }
+
+    pub(crate) fn is_on(&self) -> bool {
+        self.mode != SchemaDrift::Ignore
+    }
+
+    /// Compare what was `sent` to `path` with what `endpoint` kept of it
+    pub(crate) fn check<T: Serialize>(
+        &self, endpoint: &str, path: &str, sent: &Value, kept: &T,
+    ) {
+        let Ok(kept) = serde_json::to_value(kept) else {
+            return;
+        };
+        let drift = Drift::between(sent, &kept);
+        if drift.is_empty() {
+            return;
+        }
+        let new = self
+            .seen
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(endpoint.to_owned())
+            .or_default()
+            .merge(drift);
+        if self.mode == SchemaDrift::Log && !new.is_empty() {
+            tracing::warn!(
+                endpoint,
+                path,
+                unknown = ?new.unknown,
+                missing = ?new.missing,
+                "response does not match the struct"
+            );
+        }
+    }
+
+    pub(crate) fn report(&self) -> DriftReport {
+        self.seen.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
@@ -2,10 +2,12 @@
 // This file is part of RNAAPI Rust API Client Library, licensed
 // under the GNU General Public License v3.0
 #![allow(clippy::too_many_arguments)]
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 
 use crate::NaApiError;
-use crate::{EndpointGetArgs, EndpointGetOne, NaClient};
+use crate::num::{Money, num_or_string};
+use crate::{EndpointGetOne, NaClient};
 use async_trait::async_trait;
 
 ///
@@ -13,7 +15,7 @@ use async_trait::async_trait;
 ///
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, EndpointGetOne)]
 #[serde(rename_all = "snake_case")]
-#[getone(path = "account/details", args = 0)]
+#[getone(path = "account/details")]
 pub struct Details {
     pub result: String,
     pub userid: String,
@@ -49,8 +51,22 @@ pub struct Details {
     pub cclastfour: String,
     pub groupid: String,
     pub status: String,
-    pub credit: String,
+    /// `None` when the account has no credit, sent as `""` or `null`
+    #[serde(default, with = "num_or_string::option")]
+    pub credit: Option<Money>,
     #[serde(rename = "allowSingleSignOn")]
     pub allowsinglesignon: String,
+    /// IE `Date: 14/10/2025 09:12<br>IP Address: ...<br>Host: ...`
     pub lastlogin: String,
 }
+
+impl Details {
+    /// When the last login was, from the `Date:` part of `lastlogin`
+    pub fn last_login(&self) -> Option<NaiveDateTime> {
+        let date = self
+            .lastlogin
+            .split("<br>")
+            .find_map(|part| part.trim().strip_prefix("Date:"))?;
+        NaiveDateTime::parse_from_str(date.trim(), "%d/%m/%Y %H:%M").ok()
+    }
+}
@@ -3,10 +3,13 @@
 // under the GNU General Public License v3.0
 #![allow(clippy::too_many_arguments)]
 
+use chrono::{NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Serialize};
 
 use crate::NaApiError;
-use crate::{EndpointGetAll, EndpointGetArgs, NaClient};
+use crate::dates;
+use crate::num::Money;
+use crate::{EndpointGetAll, ItemStream, NaClient};
 use async_trait::async_trait;
 
 ///
@@ -14,24 +17,23 @@ use async_trait::async_trait;
 ///
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, EndpointGetAll)]
 #[serde(rename_all = "snake_case")]
-#[getall(path = "account/invoices", args = 0)]
+#[getall(path = "account/invoices")]
 pub struct Invoices {
     pub id: u32,
     pub userid: u32,
-    pub date: String,
-    pub duedate: String,
-    pub subtotal: String,
-    pub credit: String,
+    #[serde(default, with = "dates::date")]
+    pub date: Option<NaiveDate>,
+    #[serde(default, with = "dates::date")]
+    pub duedate: Option<NaiveDate>,
+    pub subtotal: Money,
+    pub credit: Money,
     pub status: String,
     pub paymentmethod: String,
-    // #[serde(with = "custom_datetime_format_seconds")]
-    // pub datepaid: NaiveDateTime,
-    // #[serde(with = "custom_datetime_format_seconds")]
-    // pub created_at: NaiveDateTime,
-    // #[serde(with = "custom_datetime_format_seconds")]
-    // pub updated_at: NaiveDateTime,
-    //
-    // pub datepaid: String,
-    // pub created_at: String,
-    // pub updated_at: String,
+    /// `None` while unpaid
+    #[serde(default, with = "dates::datetime")]
+    pub datepaid: Option<NaiveDateTime>,
+    #[serde(default, with = "dates::datetime")]
+    pub created_at: Option<NaiveDateTime>,
+    #[serde(default, with = "dates::datetime")]
+    pub updated_at: Option<NaiveDateTime>,
 }
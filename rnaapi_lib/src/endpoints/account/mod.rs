@@ -1,3 +1,4 @@
+#[allow(clippy::module_inception)]
 pub mod account;
 pub mod invoices;
 pub mod ssh;
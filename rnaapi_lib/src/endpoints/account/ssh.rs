@@ -5,7 +5,11 @@
 use serde::{Deserialize, Serialize};
 
 use crate::NaApiError;
-use crate::{EndpointGetAll, EndpointGetArgs, EndpointGetOne, NaClient};
+use crate::ids::SshKeyId;
+use crate::{
+    EndpointCreate, EndpointDelete, EndpointGetAll, EndpointGetOne, ItemStream,
+    NaClient, RequestBody,
+};
 use async_trait::async_trait;
 
 ///
@@ -19,13 +23,27 @@ use async_trait::async_trait;
     Deserialize,
     EndpointGetAll,
     EndpointGetOne,
+    EndpointCreate,
+    EndpointDelete,
 )]
 #[serde(rename_all = "snake_case")]
-#[getone(path = "account/ssh_key/{}", args = 1)]
-#[getall(path = "account/ssh_keys", args = 0)]
+#[getone(path = "account/ssh_key/{key_id}", params(key_id: SshKeyId))]
+#[getall(path = "account/ssh_keys")]
+#[create(path = "account/ssh_key", request = SSHKeyRequest)]
+#[delete(path = "account/ssh_key/{key_id}", params(key_id: SshKeyId))]
 pub struct SSHKeys {
-    pub id: u32,
+    pub id: SshKeyId,
     pub ssh_key: String,
     pub fingerprint: String,
     pub name: String,
 }
+
+///
+/// Body for adding a new SSH key
+///
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SSHKeyRequest {
+    pub name: String,
+    pub ssh_key: String,
+}
@@ -0,0 +1,230 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+use serde::{Deserialize, Serialize};
+
+use crate::endpoints::{Image, Location, Package, SSHKeys, Server, Sizes};
+use crate::endpoints::{SizesGetAllParams, SrvJob};
+use crate::errors::NaApiError;
+use crate::ids::{LocationId, MbPkgId, OsId, PlanId, SshKeyId};
+use crate::{EndpointGetAll, NaClient, RequestBody};
+
+//
+// BuildRequest struct
+// URL: https://vapi2.netactuate.com/api/cloud/server/build/{mbpkgid}
+// URL: https://vapi2.netactuate.com/api/cloud/server/build
+//
+/// What to build, sent form encoded
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BuildRequest {
+    /// the package to build on, `None` orders a new one
+    #[serde(skip)]
+    pub mbpkgid: Option<MbPkgId>,
+    pub location_id: LocationId,
+    pub plan_id: PlanId,
+    /// the image, see `Image`
+    pub os_id: OsId,
+    /// fully qualified, IE `web2.example.net`
+    pub hostname: String,
+    /// keys to put on the server, see `SSHKeys`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ssh_key_ids: Vec<SshKeyId>,
+    /// handed to cloud-init as is
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_data: Option<String>,
+}
+
+impl BuildRequest {
+    /// A build of a new package, see `on_package` to reuse one
+    pub fn new(
+        location_id: LocationId, plan_id: PlanId, os_id: OsId,
+        hostname: impl Into<String>,
+    ) -> Self {
+        BuildRequest {
+            mbpkgid: None,
+            location_id,
+            plan_id,
+            os_id,
+            hostname: hostname.into(),
+            ssh_key_ids: Vec::new(),
+            user_data: None,
+        }
+    }
+
+    /// Build on an existing package, whatever is on it is wiped
+    pub fn on_package(mut self, mbpkgid: MbPkgId) -> Self {
+        self.mbpkgid = Some(mbpkgid);
+        self
+    }
+
+    pub fn ssh_key(mut self, key_id: SshKeyId) -> Self {
+        self.ssh_key_ids.push(key_id);
+        self
+    }
+
+    pub fn user_data(mut self, user_data: impl Into<String>) -> Self {
+        self.user_data = Some(user_data.into());
+        self
+    }
+
+    /// `cloud/server/build/{mbpkgid}`, or `cloud/server/build` for a
+    /// new package
+    pub fn path(&self) -> String {
+        match self.mbpkgid {
+            Some(mbpkgid) => format!("cloud/server/build/{mbpkgid}"),
+            None => "cloud/server/build".to_owned(),
+        }
+    }
+
+    /// Check the request against the catalog: the location is enabled,
+    /// the plan is sold there, the image and keys exist and so does the
+    /// package, when there is one
+    pub async fn validate(
+        &self, na_client: &NaClient,
+    ) -> Result<(), NaApiError> {
+        check_hostname(&self.hostname)?;
+        let (locations, images) = tokio::try_join!(
+            Location::get_all(na_client, ()),
+            Image::get_all(na_client, ()),
+        )?;
+
+        match locations.iter().find(|l| l.id == self.location_id) {
+            None => {
+                return Err(invalid(
+                    "location_id",
+                    format!("location {} does not exist", self.location_id),
+                ));
+            }
+            Some(location) if location.disabled != 0 => {
+                return Err(invalid(
+                    "location_id",
+                    format!("{} is not taking new servers", location.name),
+                ));
+            }
+            Some(_) => {}
+        }
+        let params = SizesGetAllParams {
+            location_id: self.location_id,
+        };
+        let sizes = match Sizes::get_all(na_client, params).await {
+            // nothing sold there
+            Err(e) if e.is_not_found() => Vec::new(),
+            sizes => sizes?,
+        };
+        match sizes.iter().find(|s| s.plan_id == self.plan_id) {
+            None => {
+                return Err(invalid(
+                    "plan_id",
+                    format!(
+                        "plan {} is not offered in location {}",
+                        self.plan_id, self.location_id
+                    ),
+                ));
+            }
+            Some(size) if size.available <= 0.0 => {
+                return Err(invalid(
+                    "plan_id",
+                    format!(
+                        "{} is sold out in location {}",
+                        size.plan, self.location_id
+                    ),
+                ));
+            }
+            Some(_) => {}
+        }
+        if !images.iter().any(|i| i.id == self.os_id) {
+            return Err(invalid(
+                "os_id",
+                format!("image {} does not exist", self.os_id),
+            ));
+        }
+
+        if !self.ssh_key_ids.is_empty() {
+            let keys = SSHKeys::get_all(na_client, ()).await?;
+            if let Some(missing) = self
+                .ssh_key_ids
+                .iter()
+                .find(|id| !keys.iter().any(|k| k.id == **id))
+            {
+                return Err(invalid(
+                    "ssh_key_ids",
+                    format!("SSH key {missing} does not exist"),
+                ));
+            }
+        }
+        if let Some(mbpkgid) = self.mbpkgid {
+            let packages = Package::get_all(na_client, ()).await?;
+            match packages.iter().find(|p| p.mbpkgid == mbpkgid) {
+                None => {
+                    return Err(invalid(
+                        "mbpkgid",
+                        format!("package {mbpkgid} does not exist"),
+                    ));
+                }
+                Some(package) if package.locked != 0 => {
+                    return Err(invalid(
+                        "mbpkgid",
+                        format!("package {mbpkgid} is locked"),
+                    ));
+                }
+                Some(_) => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Server {
+    /// Validate `request`, see `BuildRequest::validate`, and submit it.
+    /// Follow the job with `SrvJob::get_one`, for a new package the job
+    /// carries the `mbpkgid` it was given
+    ///
+    /// ```rust,no_run
+    /// # async fn build(client: &rnaapi::NaClient) -> Result<(), rnaapi::errors::NaApiError> {
+    /// use rnaapi::endpoints::{BuildRequest, Server};
+    /// use rnaapi::{LocationId, MbPkgId, OsId, PlanId, SshKeyId};
+    ///
+    /// let request =
+    ///     BuildRequest::new(LocationId(3), PlanId(101), OsId(12), "web2.example.net")
+    ///         .on_package(MbPkgId(7002))
+    ///         .ssh_key(SshKeyId(301))
+    ///         .user_data("#cloud-config\npackage_upgrade: true\n");
+    /// let job = Server::build(client, &request).await?;
+    /// println!("build job {} is {}", job.id, job.status);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn build(
+        na_client: &NaClient, request: &BuildRequest,
+    ) -> Result<SrvJob, NaApiError> {
+        request.validate(na_client).await?;
+        let body = RequestBody::form(request)?;
+        let mut job: SrvJob =
+            na_client.post_as("SrvJob", &request.path(), body).await?;
+        if job.mbpkgid.is_none() {
+            job.mbpkgid = request.mbpkgid;
+        }
+        Ok(job)
+    }
+}
+
+fn invalid(field: &'static str, message: String) -> NaApiError {
+    NaApiError::Validation { field, message }
+}
+
+/// Labels of letters, digits and inner hyphens, 253 characters at most
+fn check_hostname(hostname: &str) -> Result<(), NaApiError> {
+    let label_ok = |label: &str| {
+        (1..=63).contains(&label.len())
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    };
+    if hostname.len() > 253 || !hostname.split('.').all(label_ok) {
+        return Err(invalid(
+            "hostname",
+            format!("{hostname:?} is not a valid host name"),
+        ));
+    }
+    Ok(())
+}
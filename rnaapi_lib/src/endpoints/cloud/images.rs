@@ -1,27 +1,34 @@
 // Copyright (C) 2025 Dennis Durling
 // This file is part of RNAAPI Rust API Client Library, licensed
 // under the GNU General Public License v3.0
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 
+use crate::dates;
 use crate::errors::NaApiError;
-use crate::{EndpointGetAll, EndpointGetArgs, NaClient};
+use crate::ids::OsId;
+use crate::num::num_or_string;
+use crate::{EndpointGetAll, ItemStream, NaClient};
 use async_trait::async_trait;
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, EndpointGetAll)]
 #[serde(rename_all = "snake_case")]
-#[getall(path = "cloud/images", args = 0)]
+#[getall(path = "cloud/images")]
 pub struct Image {
-    pub id: u32,
+    pub id: OsId,
     pub os: Option<String>,
     pub description: Option<String>,
     pub size: Option<String>,
     pub subtype: Option<String>,
-    pub created: Option<String>,
+    #[serde(default, with = "dates::datetime")]
+    pub created: Option<NaiveDateTime>,
     pub category: Option<String>,
-    pub updated: Option<String>,
+    #[serde(default, with = "dates::datetime")]
+    pub updated: Option<NaiveDateTime>,
     pub iso: Option<String>,
     pub bits: Option<String>,
     pub tech: Option<String>,
     pub icon: Option<String>,
+    #[serde(default, with = "num_or_string::option")]
     pub private: Option<u32>,
 }
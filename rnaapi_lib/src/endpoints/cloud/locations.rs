@@ -5,14 +5,15 @@
 use serde::{Deserialize, Serialize};
 
 use crate::errors::NaApiError;
-use crate::{EndpointGetAll, EndpointGetArgs, NaClient};
+use crate::ids::LocationId;
+use crate::{EndpointGetAll, ItemStream, NaClient};
 use async_trait::async_trait;
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, EndpointGetAll)]
 #[serde(rename_all = "snake_case")]
-#[getall(path = "cloud/locations", args = 0)]
+#[getall(path = "cloud/locations")]
 pub struct Location {
-    pub id: u32,
+    pub id: LocationId,
     pub name: String,
     pub iata_code: String,
     pub continent: String,
@@ -1,9 +1,11 @@
+pub mod build;
 pub mod images;
 pub mod locations;
 pub mod packages;
 pub mod servers;
 pub mod sizes;
 
+pub use build::*;
 pub use images::*;
 pub use locations::*;
 pub use packages::*;
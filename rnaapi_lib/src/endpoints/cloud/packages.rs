@@ -5,14 +5,16 @@
 use serde::{Deserialize, Serialize};
 
 use crate::errors::NaApiError;
-use crate::{EndpointGetAll, EndpointGetArgs, NaClient};
+use crate::ids::MbPkgId;
+use crate::status::{PackageStatus, ServerState};
+use crate::{EndpointGetAll, ItemStream, NaClient};
 use async_trait::async_trait;
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, EndpointGetAll)]
-#[getall(path = "cloud/packages", args = 0)]
+#[getall(path = "cloud/packages")]
 pub struct Package {
-    pub mbpkgid: u32,
-    pub package_status: String,
+    pub mbpkgid: MbPkgId,
+    pub package_status: PackageStatus,
     pub fqdn: String,
     pub name: String,
     pub gid: u32,
@@ -25,7 +27,7 @@ pub struct Package {
     pub city: String,
     pub ip: String,
     pub installed: u32,
-    pub state: String,
+    pub state: ServerState,
     pub uptime: String,
     pub os: String,
     pub is_building: u32,
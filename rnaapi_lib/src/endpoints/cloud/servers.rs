@@ -2,10 +2,19 @@
 // This file is part of RNAAPI Rust API Client Library, licensed
 // under the GNU General Public License v3.0
 #![allow(clippy::too_many_arguments)]
+use chrono::{NaiveDate, NaiveDateTime};
+use ipnet::{Ipv4AddrRange, Ipv4Net, Ipv6AddrRange, Ipv6Net};
 use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 
+use crate::dates;
 use crate::errors::NaApiError;
-use crate::{EndpointGetAll, EndpointGetArgs, EndpointGetOne, NaClient};
+use crate::ids::{JobId, LocationId, MbPkgId, OsId, PlanId};
+use crate::net::{self, MacAddr, Netmask, PrefixLen};
+use crate::status::{JobStatus, ServerState, ServerStatus};
+use crate::{
+    EndpointGetAll, EndpointGetOne, ItemStream, NaClient, RequestBody,
+};
 use async_trait::async_trait;
 
 //
@@ -21,26 +30,84 @@ use async_trait::async_trait;
     EndpointGetAll,
 )]
 #[serde(rename_all = "snake_case")]
-#[getall(path = "cloud/servers", args = 0)]
-#[getone(path = "cloud/server?mbpkgid={}", args = 1)]
+#[getall(path = "cloud/servers")]
+#[getone(path = "cloud/server", query(mbpkgid: MbPkgId))]
 pub struct Server {
     pub city: String,
     pub fqdn: String,
     #[serde(rename = "domU_package")]
     pub domu_package: u32,
-    pub mbpkgid: u32,
-    pub os_id: u32,
-    pub location_id: u32,
-    pub ip: String,
-    pub ipv6: String,
-    pub plan_id: u32,
+    pub mbpkgid: MbPkgId,
+    pub os_id: OsId,
+    pub location_id: LocationId,
+    /// `None` until the package is built, and after it is cancelled
+    #[serde(default, with = "net::optional")]
+    pub ip: Option<Ipv4Addr>,
+    /// `None` when the server has no IPv6 address
+    #[serde(default, with = "net::optional")]
+    pub ipv6: Option<Ipv6Addr>,
+    pub plan_id: PlanId,
     pub pkg_id: u32,
-    pub state: String,
-    pub status: String,
+    pub state: ServerState,
+    pub status: ServerStatus,
     pub uptime: String,
     pub installed: u32,
-    pub nic1_mac: String,
-    pub nic2_mac: String,
+    /// `None` until the package is built, like `ip`
+    #[serde(default, with = "net::optional")]
+    pub nic1_mac: Option<MacAddr>,
+    /// `None` when there is no second NIC
+    #[serde(default, with = "net::optional")]
+    pub nic2_mac: Option<MacAddr>,
+}
+
+impl Server {
+    /// Whether `addr` is in the subnet of the server's main address.
+    /// `ipv4` and `ipv6` are the server's lists from `IPv4::get_all` and
+    /// `IPv6::get_all`, they carry the netmasks
+    pub fn in_subnet(
+        &self, addr: IpAddr, ipv4: &[IPv4], ipv6: &[IPv6],
+    ) -> bool {
+        match addr {
+            IpAddr::V4(addr) => ipv4
+                .iter()
+                .filter(|net| Some(net.ip) == self.ip)
+                .any(|net| net.contains(&addr)),
+            IpAddr::V6(addr) => ipv6
+                .iter()
+                .filter(|net| Some(net.ip) == self.ipv6)
+                .any(|net| net.contains(&addr)),
+        }
+    }
+
+    /// Power the server on. The job it hands back can be followed with
+    /// `SrvJob::get_one`
+    pub async fn boot(
+        na_client: &NaClient, mbpkgid: MbPkgId,
+    ) -> Result<SrvJob, NaApiError> {
+        Server::power(na_client, "start", mbpkgid).await
+    }
+
+    /// Shut the server down, see `boot`
+    pub async fn shutdown(
+        na_client: &NaClient, mbpkgid: MbPkgId,
+    ) -> Result<SrvJob, NaApiError> {
+        Server::power(na_client, "shutdown", mbpkgid).await
+    }
+
+    /// Reboot the server, see `boot`
+    pub async fn reboot(
+        na_client: &NaClient, mbpkgid: MbPkgId,
+    ) -> Result<SrvJob, NaApiError> {
+        Server::power(na_client, "reboot", mbpkgid).await
+    }
+
+    /// POST `cloud/server/{action}/{mbpkgid}`, no body, the job comes back
+    async fn power(
+        na_client: &NaClient, action: &str, mbpkgid: MbPkgId,
+    ) -> Result<SrvJob, NaApiError> {
+        let path = format!("cloud/server/{action}/{mbpkgid}");
+        na_client.post_as("SrvJob", &path, RequestBody::Empty).await
+    }
 }
 
 // Job struct
@@ -57,15 +124,17 @@ pub struct Server {
     EndpointGetOne,
 )]
 #[serde(rename_all = "snake_case")]
-#[getall(path = "cloud/server/{}/jobs", args = 1)]
-#[getone(path = "cloud/server/{}/jobs/{}", args = 2)]
+#[getall(path = "cloud/server/{mbpkgid}/jobs", params(mbpkgid: MbPkgId))]
+#[getone(path = "cloud/server/{mbpkgid}/jobs/{job_id}", params(mbpkgid: MbPkgId, job_id: JobId))]
 pub struct SrvJob {
-    pub id: u32,
-    // #[serde(with = "custom_datetime_format_seconds")]
-    // pub ts_insert: NaiveDateTime,
-    pub ts_insert: String,
+    pub id: JobId,
+    #[serde(default, with = "dates::datetime")]
+    pub ts_insert: Option<NaiveDateTime>,
     pub command: String,
-    pub status: u32,
+    pub status: JobStatus,
+    /// the package the job runs on, only sent back by builds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mbpkgid: Option<MbPkgId>,
 }
 
 //
@@ -74,9 +143,9 @@ pub struct SrvJob {
 //
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, EndpointGetOne)]
 #[serde(rename_all = "snake_case")]
-#[getone(path = "cloud/status/{}", args = 1)]
+#[getone(path = "cloud/status/{mbpkgid}", params(mbpkgid: MbPkgId))]
 pub struct SrvStatus {
-    pub status: String,
+    pub status: ServerState,
 }
 
 //
@@ -85,15 +154,32 @@ pub struct SrvStatus {
 //
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, EndpointGetAll)]
 #[serde(rename_all = "snake_case")]
-#[getall(path = "cloud/ipv4?mbpkgid={}", args = 1)]
+#[getall(path = "cloud/ipv4", query(mbpkgid: MbPkgId))]
 pub struct IPv4 {
     pub id: u32,
     pub primary: u32,
     pub reverse: String,
-    pub ip: String,
-    pub netmask: String,
-    pub gateway: String,
-    pub broadcast: String,
+    pub ip: Ipv4Addr,
+    pub netmask: Netmask,
+    pub gateway: Ipv4Addr,
+    pub broadcast: Ipv4Addr,
+}
+
+impl IPv4 {
+    /// The subnet the address is in, IE `192.0.2.0/24`
+    pub fn network(&self) -> Ipv4Net {
+        Ipv4Net::new_assert(self.ip, self.netmask.prefix_len()).trunc()
+    }
+
+    /// Usable host addresses, without the network and broadcast address
+    pub fn hosts(&self) -> Ipv4AddrRange {
+        self.network().hosts()
+    }
+
+    /// Whether `addr` is in the same subnet
+    pub fn contains(&self, addr: &Ipv4Addr) -> bool {
+        self.network().contains(addr)
+    }
 }
 
 //
@@ -102,15 +188,35 @@ pub struct IPv4 {
 //
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, EndpointGetAll)]
 #[serde(rename_all = "snake_case")]
-#[getall(path = "cloud/ipv6?mbpkgid={}", args = 1)]
+#[getall(path = "cloud/ipv6", query(mbpkgid: MbPkgId))]
 pub struct IPv6 {
     pub id: u32,
     pub primary: u32,
     pub reverse: String,
-    pub ip: String,
-    pub netmask: String,
-    pub gateway: String,
-    pub broadcast: String,
+    pub ip: Ipv6Addr,
+    /// the prefix length, sent as `"64"`
+    pub netmask: PrefixLen,
+    pub gateway: Ipv6Addr,
+    /// IPv6 has no broadcast, it is usually sent empty
+    #[serde(default, with = "net::optional")]
+    pub broadcast: Option<Ipv6Addr>,
+}
+
+impl IPv6 {
+    /// The subnet the address is in, IE `2001:db8:10::/64`
+    pub fn network(&self) -> Ipv6Net {
+        Ipv6Net::new_assert(self.ip, self.netmask.get()).trunc()
+    }
+
+    /// Host addresses in the subnet, all of them as IPv6 has no broadcast
+    pub fn hosts(&self) -> Ipv6AddrRange {
+        self.network().hosts()
+    }
+
+    /// Whether `addr` is in the same subnet
+    pub fn contains(&self, addr: &Ipv6Addr) -> bool {
+        self.network().contains(addr)
+    }
 }
 
 //
@@ -119,9 +225,11 @@ pub struct IPv6 {
 //
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, EndpointGetAll)]
 #[serde(rename_all = "snake_case")]
-#[getall(path = "cloud/servermonthlybw/{}", args = 1)]
+#[getall(path = "cloud/servermonthlybw/{mbpkgid}", params(mbpkgid: MbPkgId))]
 pub struct MonthlyBw {
-    pub date: String,
+    /// first day of the month the totals are for
+    #[serde(with = "dates::month")]
+    pub date: NaiveDate,
     pub rx: u32,
     pub tx: u32,
 }
@@ -4,20 +4,28 @@
 use serde::{Deserialize, Serialize};
 
 use crate::errors::NaApiError;
-use crate::{EndpointGetAll, EndpointGetArgs, NaClient};
+use crate::ids::{LocationId, PlanId};
+use crate::num::{Money, num_or_string};
+use crate::{EndpointGetAll, ItemStream, NaClient};
 use async_trait::async_trait;
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, EndpointGetAll)]
 #[serde(rename_all = "snake_case")]
-#[getall(path = "cloud/sizes/{}", args = 1)]
+#[getall(path = "cloud/sizes/{location_id}", params(location_id: LocationId))]
 pub struct Sizes {
-    pub plan_id: u32,
+    pub plan_id: PlanId,
     pub plan: String,
-    pub ram: String,
-    pub disk: String,
-    pub transfer: String,
-    pub price: String,
+    #[serde(rename = "ram", with = "num_or_string")]
+    pub ram_mb: u32,
+    #[serde(rename = "disk", with = "num_or_string")]
+    pub disk_gb: u32,
+    #[serde(rename = "transfer", with = "num_or_string")]
+    pub transfer_gb: u32,
+    /// monthly
+    pub price: Money,
+    #[serde(with = "num_or_string")]
     pub cpu: u32,
-    pub port: String,
+    #[serde(rename = "port", with = "num_or_string")]
+    pub port_mbps: u32,
     pub available: f32,
 }
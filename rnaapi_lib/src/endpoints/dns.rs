@@ -5,20 +5,15 @@
 use serde::{Deserialize, Serialize};
 
 use crate::errors::NaApiError;
-use crate::{EndpointGetAll, EndpointGetArgs, EndpointGetOne, NaClient};
+use crate::ids::{RecordId, ZoneId};
+use crate::num::num_or_string;
+use crate::status::ZoneType;
+use crate::{
+    EndpointCreate, EndpointDelete, EndpointGetAll, EndpointGetOne,
+    EndpointUpdate, ItemStream, NaClient, RequestBody,
+};
 use async_trait::async_trait;
 
-//
-// Zone ttl key type since it changes between
-// a single zone get and a get all zones
-//
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-#[serde(untagged)]
-pub enum TtlType {
-    StringKey(String),
-    IntegerKey(u32),
-}
-
 //
 // Just Zone struct (from ID)
 //
@@ -32,15 +27,26 @@ pub enum TtlType {
     EndpointGetAll,
 )]
 #[serde(rename_all = "snake_case")]
-#[getone(path = "dns/zone/{}", args = 1)]
-#[getall(path = "dns/zones?type=NATIVE", args = 0)]
+#[getone(path = "dns/zone/{zone_id}", params(zone_id: ZoneId))]
+#[getall(
+    path = "dns/zones",
+    query(
+        /// only zones of this type, all when `None`. `None` is also the
+        /// default, before 3.0 `get_all` only ever listed `NATIVE` zones,
+        /// pass `Some(ZoneType::Native)` to keep that
+        #[serde(rename = "type")]
+        zone_type: Option<ZoneType>,
+    )
+)]
 pub struct Zone {
-    pub id: u32,
+    pub id: ZoneId,
     pub name: String,
     #[serde(rename = "type")]
-    pub zone_type: String,
+    pub zone_type: ZoneType,
     pub master: Option<u32>,
-    pub ttl: Option<TtlType>,
+    /// a string from `get_one`, a number from `get_all`
+    #[serde(default, with = "num_or_string::option")]
+    pub ttl: Option<u32>,
     pub soa: Option<SOA>,
     pub records: Option<Vec<Record>>,
     pub ns: Option<Vec<Record>>,
@@ -54,11 +60,16 @@ pub struct Zone {
 pub struct SOA {
     pub primary: String,
     pub hostmaster: String,
-    pub serial: String,
-    pub refresh: String,
-    pub retry: String,
-    pub expire: String,
-    pub default_ttl: String,
+    #[serde(with = "num_or_string")]
+    pub serial: u32,
+    #[serde(with = "num_or_string")]
+    pub refresh: u32,
+    #[serde(with = "num_or_string")]
+    pub retry: u32,
+    #[serde(with = "num_or_string")]
+    pub expire: u32,
+    #[serde(with = "num_or_string")]
+    pub default_ttl: u32,
 }
 
 //
@@ -72,17 +83,40 @@ pub struct SOA {
     Deserialize,
     EndpointGetAll,
     EndpointGetOne,
+    EndpointCreate,
+    EndpointUpdate,
+    EndpointDelete,
 )]
 #[serde(rename_all = "snake_case")]
-#[getone(path = "dns/record/{}", args = 1)]
-#[getall(path = "dns/records/{}", args = 1)]
+#[getone(path = "dns/record/{record_id}", params(record_id: RecordId))]
+#[getall(path = "dns/records/{zone_id}", params(zone_id: ZoneId))]
+#[create(path = "dns/record", request = RecordRequest)]
+#[update(path = "dns/record/{record_id}", params(record_id: RecordId), request = RecordRequest)]
+#[delete(path = "dns/record/{record_id}", params(record_id: RecordId))]
 pub struct Record {
-    pub id: u32,
+    pub id: RecordId,
     pub name: String,
     #[serde(rename = "type")]
     pub record_type: String,
+    #[serde(default, with = "num_or_string::option")]
     pub ttl: Option<u32>,
+    #[serde(default, with = "num_or_string::option")]
     pub prio: Option<u32>,
     pub content: String,
-    pub domain_id: Option<u32>,
+    pub domain_id: Option<ZoneId>,
+}
+
+//
+// Body for creating or updating a Record
+//
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct RecordRequest {
+    pub domain_id: ZoneId,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub record_type: String,
+    pub content: String,
+    pub ttl: Option<u32>,
+    pub prio: Option<u32>,
 }
@@ -0,0 +1,130 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! The `{"code", "message", "data"}` wrapper every API response comes in
+//!
+//! Decoding it typed, straight from the response bytes, builds the
+//! endpoint structs in one pass instead of going through a
+//! `serde_json::Value` first
+//!
+//! ```rust
+//! use rnaapi::envelope::Envelope;
+//!
+//! let body = br#"{"code": 200, "message": "ok", "data": [1, 2]}"#;
+//! let envelope: Envelope<Vec<u32>> =
+//!     Envelope::from_slice("numbers", "test", body).unwrap();
+//! assert_eq!(envelope.data, Some(vec![1, 2]));
+//! ```
+use serde::de::{DeserializeOwned, Deserializer};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+
+use crate::errors::NaApiError;
+use crate::{NaClient, json_pointer};
+
+/// A response body with `data` decoded as `T`
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(bound(deserialize = "T: Deserialize<'de>"))]
+pub struct Envelope<T> {
+    /// sent as a number or as a numeric string, depending on the endpoint
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<Value>,
+    /// set on paged lists, 1 based
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+    /// how many pages the list has, set along with `page`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pages: Option<u32>,
+    /// `None` only when the key is missing, a `null` is handed to `T`
+    #[serde(
+        default,
+        deserialize_with = "present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub data: Option<T>,
+}
+
+impl<T: DeserializeOwned> Envelope<T> {
+    /// Decode a response body, `endpoint` and `path` are only used to say
+    /// where a mismatch came from. The pointer in the error is into the
+    /// whole body, IE `/data/0/mbpkgid`
+    pub fn from_slice(
+        endpoint: &str, path: &str, body: &[u8],
+    ) -> Result<Self, NaApiError> {
+        let error = |pointer: String, source: serde_json::Error| {
+            NaApiError::Deserialize {
+                endpoint: endpoint.to_owned(),
+                path: path.to_owned(),
+                pointer,
+                source: Arc::new(source),
+            }
+        };
+        let mut de = serde_json::Deserializer::from_slice(body);
+        let envelope = serde_path_to_error::deserialize(&mut de)
+            .map_err(|e| error(json_pointer(e.path()), e.into_inner()))?;
+        de.end().map_err(|e| error(String::new(), e))?;
+        Ok(envelope)
+    }
+}
+
+/// Walks the pages of a list. The first request goes out as is, while
+/// the responses say there are more pages the next one is asked for with
+/// `page=N`. Lists that are not paged are done after one response
+#[derive(Clone, Debug)]
+pub(crate) struct Pager {
+    base: String,
+    path: String,
+    asked: Option<u32>,
+}
+
+impl Pager {
+    pub(crate) fn new(path: &str) -> Self {
+        Pager {
+            base: path.to_owned(),
+            path: path.to_owned(),
+            asked: None,
+        }
+    }
+
+    /// Path for the request to make next
+    pub(crate) fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Move on after a response, `false` once that was the last page
+    pub(crate) fn next(
+        &mut self, page: Option<u32>, pages: Option<u32>,
+    ) -> Result<bool, NaApiError> {
+        // a server ignoring `page` would otherwise send us round forever
+        if let Some(asked) = self.asked
+            && page != Some(asked)
+        {
+            return Err(NaApiError::UnknownError(format!(
+                "Asked {} for page {asked}, got {page:?}",
+                self.base
+            )));
+        }
+        match (page, pages) {
+            (Some(page), Some(pages)) if page < pages => {
+                let next = page + 1;
+                self.path =
+                    NaClient::with_query(&self.base, &[("page", next)])?;
+                self.asked = Some(next);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+/// Any `data` that is there counts, including `null`
+fn present<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
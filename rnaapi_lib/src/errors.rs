@@ -1,6 +1,8 @@
 // Copyright (C) 2025 Dennis Durling
 // This file is part of RNAAPI Rust API Client Library, licensed
 // under the GNU General Public License v3.0
+use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Clone, Debug, Error)]
@@ -12,4 +14,96 @@ pub enum NaApiError {
     APIKeyInvalid(String),
     #[error("{0}")]
     UnknownError(String),
+    /// The request never got a response (dns, connect, tls, reset...)
+    #[error("Failed to reach {path}: {source}")]
+    Transport {
+        path: String,
+        #[source]
+        source: Arc<dyn std::error::Error + Send + Sync>,
+    },
+    /// The API answered with a non success HTTP status
+    #[error("HTTP {status} from {path}{}", message_suffix(.message))]
+    HttpStatus {
+        path: String,
+        status: u16,
+        message: Option<String>,
+        /// how long the API asked us to wait, from `Retry-After`
+        retry_after: Option<Duration>,
+    },
+    /// The response could not be deserialized, `pointer` is the JSON
+    /// pointer of the offending value, empty for the whole body
+    #[error(
+        "Failed to deserialize {endpoint} from {path} at '{pointer}': {source}"
+    )]
+    Deserialize {
+        endpoint: String,
+        path: String,
+        pointer: String,
+        #[source]
+        source: Arc<serde_json::Error>,
+    },
+    /// The API answered with its own `code`/`message` error envelope
+    #[error("API error {code} from {path}: {message}")]
+    Api {
+        path: String,
+        code: i64,
+        message: String,
+    },
+    /// A request was turned down before it was sent, IE a `BuildRequest`
+    /// for a plan the location does not offer
+    #[error("Invalid {field}: {message}")]
+    Validation {
+        field: &'static str,
+        message: String,
+    },
+}
+
+impl NaApiError {
+    /// HTTP status of the failed response, if there was one
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            NaApiError::HttpStatus { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// The numeric `code` sent back by the API, if there was one
+    pub fn api_code(&self) -> Option<i64> {
+        match self {
+            NaApiError::Api { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+
+    /// Resource does not exist, either by HTTP status or API code
+    pub fn is_not_found(&self) -> bool {
+        self.status() == Some(404) || self.api_code() == Some(404)
+    }
+
+    /// We are sending requests faster than the API allows
+    pub fn is_rate_limited(&self) -> bool {
+        self.status() == Some(429) || self.api_code() == Some(429)
+    }
+
+    /// The API itself failed, 5xx
+    pub fn is_server_error(&self) -> bool {
+        matches!(self.status(), Some(500..=599))
+    }
+
+    /// Turned down before it was sent, see `Validation`
+    pub fn is_validation(&self) -> bool {
+        matches!(self, NaApiError::Validation { .. })
+    }
+
+    /// The request did not make it to the API or back
+    pub fn is_transport(&self) -> bool {
+        matches!(self, NaApiError::Transport { .. })
+    }
+}
+
+fn message_suffix(message: &Option<String>) -> String {
+    match message {
+        Some(message) => format!(": {message}"),
+        None => String::new(),
+    }
 }
@@ -0,0 +1,231 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! Record real API responses to fixture files and replay them later
+//!
+//! `RecordingTransport` sits in front of another transport and writes
+//! every exchange to `<dir>/<method>_<path>.json` with the API key
+//! scrubbed. `ReplayTransport` answers from those files, so tests can
+//! run the real endpoint code against what the API actually returned
+//!
+//! ```rust,no_run
+//! # async fn record() -> Result<(), rnaapi::errors::NaApiError> {
+//! use rnaapi::{EndpointGetAll, NaClient};
+//! use rnaapi::endpoints::Server;
+//!
+//! let client = NaClient::builder("<your api key>", "https://vapi2.netactuate.com/api/")
+//!     .record_fixtures("tests/fixtures")
+//!     .build()?;
+//! Server::get_all(&client, ()).await?;
+//! # Ok(())
+//! # }
+//! ```
+use async_trait::async_trait;
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::errors::NaApiError;
+use crate::secret::ApiKey;
+use crate::transport::{HttpRequest, HttpResponse, Transport};
+
+/// One recorded exchange, as stored on disk
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Fixture {
+    pub method: String,
+    /// endpoint path as handed to `NaClient`, never includes the key
+    pub path: String,
+    pub status: u16,
+    /// the response body, when it was JSON
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+    /// the response body, when it was not
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+impl Fixture {
+    /// Capture a response, scrubbing `api_key` out of the body
+    pub fn new(
+        request: &HttpRequest, response: &HttpResponse, api_key: &ApiKey,
+    ) -> Self {
+        let text = api_key.redact(&String::from_utf8_lossy(&response.body));
+        let (body, text) = match serde_json::from_str(&text) {
+            Ok(body) => (Some(body), None),
+            Err(_) => (None, Some(text)),
+        };
+        Fixture {
+            method: request.method.to_string(),
+            path: request.path.clone(),
+            status: response.status.as_u16(),
+            body,
+            text,
+        }
+    }
+
+    /// File name for a request, IE `get_cloud_server_mbpkgid_1.json`
+    pub fn file_name(method: &Method, path: &str) -> String {
+        let mut name = method.as_str().to_lowercase();
+        name.push('_');
+        for c in path.trim_matches('/').chars() {
+            if c.is_ascii_alphanumeric() {
+                name.push(c);
+            } else if !name.ends_with('_') {
+                name.push('_');
+            }
+        }
+        let name = name.trim_end_matches('_');
+        format!("{name}.json")
+    }
+
+    /// Load a single fixture file
+    pub fn load(path: &Path) -> Result<Self, NaApiError> {
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            NaApiError::UnknownError(format!(
+                "Unable to read {}: {e}",
+                path.display()
+            ))
+        })?;
+        serde_json::from_str(&text).map_err(|e| {
+            NaApiError::UnknownError(format!(
+                "Invalid fixture {}: {e}",
+                path.display()
+            ))
+        })
+    }
+
+    /// Write it to `dir`, named after the request
+    pub fn save(&self, dir: &Path) -> Result<PathBuf, NaApiError> {
+        let method =
+            Method::from_bytes(self.method.as_bytes()).unwrap_or(Method::GET);
+        let file = dir.join(Fixture::file_name(&method, &self.path));
+        let text = serde_json::to_string_pretty(self).map_err(|e| {
+            NaApiError::UnknownError(format!("Unable to encode fixture: {e}"))
+        })?;
+        std::fs::create_dir_all(dir)
+            .and_then(|_| std::fs::write(&file, text + "\n"))
+            .map_err(|e| {
+                NaApiError::UnknownError(format!(
+                    "Unable to write {}: {e}",
+                    file.display()
+                ))
+            })?;
+        Ok(file)
+    }
+
+    /// Turn it back into a response
+    pub fn response(&self) -> HttpResponse {
+        let status =
+            StatusCode::from_u16(self.status).unwrap_or(StatusCode::OK);
+        match (&self.body, &self.text) {
+            (Some(body), _) => HttpResponse::json(status, body),
+            (None, Some(text)) => HttpResponse::new(status, text.clone()),
+            (None, None) => HttpResponse::new(status, Vec::new()),
+        }
+    }
+}
+
+/// Passes requests on and writes every response to a fixture file
+#[derive(Debug)]
+pub struct RecordingTransport {
+    inner: Arc<dyn Transport>,
+    dir: PathBuf,
+    api_key: ApiKey,
+}
+
+impl RecordingTransport {
+    pub fn new(
+        inner: Arc<dyn Transport>, dir: impl Into<PathBuf>, api_key: ApiKey,
+    ) -> Self {
+        RecordingTransport {
+            inner,
+            dir: dir.into(),
+            api_key,
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for RecordingTransport {
+    async fn execute(
+        &self, request: HttpRequest,
+    ) -> Result<HttpResponse, NaApiError> {
+        let response = self.inner.execute(request.clone()).await?;
+        let fixture = Fixture::new(&request, &response, &self.api_key);
+        // a failed write should not fail the call being recorded
+        if let Err(e) = fixture.save(&self.dir) {
+            tracing::warn!(path = %request.path, "not recorded: {e}");
+        }
+        Ok(response)
+    }
+}
+
+/// Answers from fixture files, anything not recorded gets an API style 404
+#[derive(Debug, Default)]
+pub struct ReplayTransport {
+    fixtures: HashMap<(String, String), Fixture>,
+}
+
+impl ReplayTransport {
+    /// Load every `*.json` fixture in `dir`
+    pub fn load(dir: impl AsRef<Path>) -> Result<Self, NaApiError> {
+        let dir = dir.as_ref();
+        let entries = std::fs::read_dir(dir).map_err(|e| {
+            NaApiError::UnknownError(format!(
+                "Unable to read {}: {e}",
+                dir.display()
+            ))
+        })?;
+        let mut replay = ReplayTransport::default();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                replay.insert(Fixture::load(&path)?);
+            }
+        }
+        Ok(replay)
+    }
+
+    /// Add or replace a fixture
+    pub fn insert(&mut self, fixture: Fixture) {
+        let key = (fixture.method.clone(), fixture.path.clone());
+        self.fixtures.insert(key, fixture);
+    }
+
+    /// The fixture recorded for `method` on `path`
+    pub fn fixture(&self, method: &Method, path: &str) -> Option<&Fixture> {
+        self.fixtures.get(&(method.to_string(), path.to_owned()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.fixtures.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fixtures.is_empty()
+    }
+}
+
+#[async_trait]
+impl Transport for ReplayTransport {
+    async fn execute(
+        &self, request: HttpRequest,
+    ) -> Result<HttpResponse, NaApiError> {
+        match self.fixture(&request.method, &request.path) {
+            Some(fixture) => Ok(fixture.response()),
+            None => Ok(HttpResponse::json(
+                StatusCode::NOT_FOUND,
+                &serde_json::json!({
+                    "code": 404,
+                    "message": format!(
+                        "no fixture for {} {}",
+                        request.method, request.path
+                    ),
+                }),
+            )),
+        }
+    }
+}
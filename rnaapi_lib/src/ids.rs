@@ -0,0 +1,106 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! Distinct id types, so a `LocationId` can't end up where an
+//! `MbPkgId` belongs. They are plain numbers on the wire
+//!
+//! ```rust,compile_fail
+//! use rnaapi::LocationId;
+//! use rnaapi::endpoints::ServerGetOneParams;
+//!
+//! let params = ServerGetOneParams { mbpkgid: LocationId(3) };
+//! ```
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+macro_rules! id_type {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(
+            Clone,
+            Copy,
+            Debug,
+            Default,
+            PartialEq,
+            Eq,
+            PartialOrd,
+            Ord,
+            Hash,
+            Serialize,
+            Deserialize,
+        )]
+        #[serde(transparent)]
+        pub struct $name(pub u32);
+
+        impl $name {
+            pub const fn new(id: u32) -> Self {
+                $name(id)
+            }
+
+            pub const fn get(self) -> u32 {
+                self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ParseIntError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                s.parse().map($name)
+            }
+        }
+
+        impl From<u32> for $name {
+            fn from(id: u32) -> Self {
+                $name(id)
+            }
+        }
+
+        impl From<$name> for u32 {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+    };
+}
+
+id_type!(
+    /// A server (package), the `mbpkgid` all the cloud/server calls take
+    MbPkgId
+);
+id_type!(
+    /// A job run against a server, IE a build or a reboot
+    JobId
+);
+id_type!(
+    /// A DNS zone, also the `domain_id` of its records
+    ZoneId
+);
+id_type!(
+    /// A single DNS record
+    RecordId
+);
+id_type!(
+    /// A plan (size) from `cloud/sizes`
+    PlanId
+);
+id_type!(
+    /// A location from `cloud/locations`
+    LocationId
+);
+id_type!(
+    /// An OS image from `cloud/images`
+    OsId
+);
+id_type!(
+    /// An SSH key stored on the account
+    SshKeyId
+);
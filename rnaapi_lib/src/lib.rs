@@ -25,22 +25,21 @@
 //!
 //! ## Import the config that uses the environment
 //!
-//! ```rust
+//! ```rust,no_run
 //! // Simplest example
 //! use anyhow::Result;
-//! use clap::Parser;
 //! use rnaapi::NaClient;
 //! use rnaapi::config::Settings;
-//! use rnaapi::endpoints;
-//! use rnaapi::{EndpointGet, EndpointGetArgs};
-
+//! use rnaapi::endpoints::Server;
+//! use rnaapi::EndpointGetAll;
+//!
 //! #[tokio::main]
 //! async fn main() -> Result<()> {
 //!
 //!     // with above imports
-//!     let settings = Settings::new();
-//!     let client = NaClient::new(settings.api_key, settings.api_url).await;
-//!     let servers = client.get_servers().await;
+//!     let settings = Settings::new()?;
+//!     let client = NaClient::new(settings.api_key, settings.api_url).await?;
+//!     let servers = Server::get_all(&client, ()).await?;
 //!     for server in servers {
 //!         println!("fqdn: {}, mbpkgid: {}", server.fqdn, server.mbpkgid);
 //!     }
@@ -48,40 +47,221 @@
 //! }
 //! ```
 //!
+//! Endpoints that need ids take them as named fields, the derive
+//! generates a params struct for each of them
+//!
+//! ```rust,no_run
+//! # async fn jobs(client: &rnaapi::NaClient) -> anyhow::Result<()> {
+//! use rnaapi::endpoints::{SrvJob, SrvJobGetOneParams};
+//! use rnaapi::{EndpointGetOne, JobId, MbPkgId};
+//!
+//! let params = SrvJobGetOneParams {
+//!     mbpkgid: MbPkgId(7001),
+//!     job_id: JobId(90001),
+//! };
+//! let job = SrvJob::get_one(client, params).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Passing the params of another endpoint does not compile
+//!
+//! ```rust,compile_fail
+//! # async fn wrong(client: &rnaapi::NaClient) -> anyhow::Result<()> {
+//! use rnaapi::endpoints::{Server, ZoneGetOneParams};
+//! use rnaapi::{EndpointGetOne, ZoneId};
+//!
+//! let server = Server::get_one(client, ZoneGetOneParams { zone_id: ZoneId(1) }).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
 //!
 // Copyright (C) 2025 Dennis Durling
 // This file is part of RNAAPI Rust API Client Library, licensed
 // under the GNU General Public License v3.0
 use async_trait::async_trait;
+use drift::{DriftReport, DriftTracker, SchemaDrift};
+use envelope::{Envelope, Pager};
 use errors::NaApiError;
-use reqwest::ClientBuilder;
-use reqwest_hickory_resolver::HickoryResolver;
+use limit::Throttle;
+use reqwest::Method;
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderName, HeaderValue};
+use secret::ApiKey;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
 use serde_json::Value;
+use std::fmt;
 use std::sync::Arc;
+use transport::{HttpRequest, Transport};
+pub mod builder;
 pub mod config;
+pub mod dates;
+pub mod drift;
 pub mod endpoints;
+pub mod envelope;
 pub mod errors;
-pub use rnaapi_derive::{EndpointGetAll, EndpointGetOne};
+pub mod fixtures;
+pub mod ids;
+pub mod limit;
+pub mod net;
+pub mod num;
+pub mod retry;
+pub mod secret;
+#[cfg(feature = "sim")]
+pub mod sim;
+pub mod status;
+pub mod stream;
+pub mod transport;
+pub use builder::{NaClientBuilder, Resolver};
+pub use ids::{
+    JobId, LocationId, MbPkgId, OsId, PlanId, RecordId, SshKeyId, ZoneId,
+};
+pub use limit::RateLimit;
+pub use num::Money;
+pub use retry::RetryPolicy;
+pub use rnaapi_derive::{
+    EndpointCreate, EndpointDelete, EndpointGetAll, EndpointGetOne,
+    EndpointUpdate,
+};
+pub use status::{
+    JobStatus, PackageStatus, ServerState, ServerStatus, ZoneType,
+};
+pub use stream::ItemStream;
 
+/// Cheap to clone, clones share the rate limit and concurrency cap
+#[derive(Clone)]
 pub struct NaClient {
     pub address: String,
-    pub api_key: String,
-    pub http_client: reqwest::Client,
+    api_key: ApiKey,
+    key_placement: KeyPlacement,
+    retry: RetryPolicy,
+    throttle: Arc<Throttle>,
+    transport: Arc<dyn Transport>,
+    drift: Arc<DriftTracker>,
 }
 
-pub enum EndpointGetArgs {
-    NoArgs,
-    OneInt(u32),
-    TwoInt(u32, u32),
+/// Where the API key is put on each request
+///
+/// The API has always taken `?key=` in the query string, which ends up in
+/// proxy and server logs. Where the API allows it, prefer a header
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum KeyPlacement {
+    /// `?key=<api key>` appended to the url
+    #[default]
+    Query,
+    /// `<name>: <api key>` request header
+    Header(String),
+    /// `Authorization: Bearer <api key>` request header
+    Bearer,
+}
+
+impl fmt::Debug for NaClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NaClient")
+            .field("address", &self.address)
+            .field("api_key", &self.api_key)
+            .field("key_placement", &self.key_placement)
+            .field("retry", &self.retry)
+            .field("rate_limit", &self.throttle.rate_limit())
+            .field("max_concurrency", &self.throttle.max_concurrency())
+            .field("transport", &self.transport)
+            .field("schema_drift", &self.drift.mode())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Body sent along with the write capable requests
+///
+/// Most of the API takes form encoded parameters, some of the newer
+/// endpoints take JSON, so both are supported
+#[derive(Clone, Debug, PartialEq)]
+pub enum RequestBody {
+    Empty,
+    Form(Vec<(String, String)>),
+    Json(Value),
+}
+
+impl RequestBody {
+    /// Build a form body from any serializable struct
+    /// The struct has to serialize to a flat object, `None` values are
+    /// skipped, bools are sent as `1`/`0` and sequences as repeated
+    /// `name[]` keys
+    ///
+    /// ```rust
+    /// use rnaapi::RequestBody;
+    /// use serde_json::json;
+    ///
+    /// let body = json!({"name": "web", "ipv6": true, "ids": [1, 2], "ttl": null});
+    /// let RequestBody::Form(pairs) = RequestBody::form(&body).unwrap() else {
+    ///     unreachable!()
+    /// };
+    /// let pairs: Vec<_> =
+    ///     pairs.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+    /// assert_eq!(pairs, [("ids[]", "1"), ("ids[]", "2"), ("ipv6", "1"), ("name", "web")]);
+    ///
+    /// assert!(RequestBody::form(&json!({"soa": {"ttl": 1}})).is_err());
+    /// ```
+    pub fn form<T: Serialize + ?Sized>(body: &T) -> Result<Self, NaApiError> {
+        let value = serde_json::to_value(body).map_err(|e| {
+            NaApiError::UnknownError(format!("Failed to encode form: {e}"))
+        })?;
+        let Value::Object(map) = value else {
+            return Err(NaApiError::UnknownError(
+                "Form body must be a struct or a map".to_owned(),
+            ));
+        };
+        let mut pairs = Vec::with_capacity(map.len());
+        for (name, value) in map {
+            match value {
+                Value::Array(items) => {
+                    for item in items {
+                        if let Some(item) = form_value(&name, item)? {
+                            pairs.push((format!("{name}[]"), item));
+                        }
+                    }
+                }
+                value => {
+                    if let Some(value) = form_value(&name, value)? {
+                        pairs.push((name, value));
+                    }
+                }
+            }
+        }
+        Ok(RequestBody::Form(pairs))
+    }
+
+    /// Build a JSON body from any serializable struct
+    pub fn json<T: Serialize + ?Sized>(body: &T) -> Result<Self, NaApiError> {
+        let value = serde_json::to_value(body).map_err(|e| {
+            NaApiError::UnknownError(format!("Failed to encode json: {e}"))
+        })?;
+        Ok(RequestBody::Json(value))
+    }
+}
+
+/// Turn a single scalar into its form representation
+fn form_value(name: &str, value: Value) -> Result<Option<String>, NaApiError> {
+    match value {
+        Value::Null => Ok(None),
+        Value::String(s) => Ok(Some(s)),
+        Value::Bool(b) => Ok(Some(u8::from(b).to_string())),
+        Value::Number(n) => Ok(Some(n.to_string())),
+        _ => Err(NaApiError::UnknownError(format!(
+            "Form field {name} can not be nested"
+        ))),
+    }
 }
 
 #[async_trait]
 pub trait EndpointGetOne {
     type Endpoint;
+    /// What goes into the path, `()` or a struct generated by the derive
+    type Params: Send;
 
     #[allow(unused)]
     async fn get_one(
-        na_client: &NaClient, args: EndpointGetArgs,
+        na_client: &NaClient, params: Self::Params,
     ) -> Result<Self::Endpoint, NaApiError> {
         Err(NaApiError::UnknownError(
             "Get All not implemented here".to_string(),
@@ -92,95 +272,448 @@ pub trait EndpointGetOne {
 #[async_trait]
 pub trait EndpointGetAll {
     type Endpoint;
+    /// What goes into the path, `()` or a struct generated by the derive
+    type Params: Send;
 
     #[allow(unused)]
     async fn get_all(
-        na_client: &NaClient, args: EndpointGetArgs,
+        na_client: &NaClient, params: Self::Params,
     ) -> Result<Vec<Self::Endpoint>, NaApiError> {
         Err(NaApiError::UnknownError(
             "Get All not implemented here".to_string(),
         ))
     }
+
+    /// Like `get_all`, but items come out one by one as they are parsed
+    /// and later pages are only fetched once the earlier ones are used up
+    #[allow(unused)]
+    fn stream_all(
+        na_client: &NaClient, params: Self::Params,
+    ) -> ItemStream<Self::Endpoint>
+    where
+        Self::Endpoint: Send + 'static,
+    {
+        stream::error(NaApiError::UnknownError(
+            "Stream All not implemented here".to_string(),
+        ))
+    }
+}
+
+#[async_trait]
+pub trait EndpointCreate {
+    type Endpoint;
+    type Params: Send;
+    type Request: Serialize + Send + Sync;
+
+    #[allow(unused)]
+    async fn create(
+        na_client: &NaClient, params: Self::Params, request: &Self::Request,
+    ) -> Result<Self::Endpoint, NaApiError> {
+        Err(NaApiError::UnknownError(
+            "Create not implemented here".to_string(),
+        ))
+    }
+}
+
+#[async_trait]
+pub trait EndpointUpdate {
+    type Endpoint;
+    type Params: Send;
+    type Request: Serialize + Send + Sync;
+
+    #[allow(unused)]
+    async fn update(
+        na_client: &NaClient, params: Self::Params, request: &Self::Request,
+    ) -> Result<Self::Endpoint, NaApiError> {
+        Err(NaApiError::UnknownError(
+            "Update not implemented here".to_string(),
+        ))
+    }
+}
+
+#[async_trait]
+pub trait EndpointDelete {
+    type Params: Send;
+
+    #[allow(unused)]
+    async fn delete(
+        na_client: &NaClient, params: Self::Params,
+    ) -> Result<(), NaApiError> {
+        Err(NaApiError::UnknownError(
+            "Delete not implemented here".to_string(),
+        ))
+    }
 }
 
 impl NaClient {
     /// build the client to use local resolver, IE Ipv4
     pub async fn new(
-        api_key: String, address: String,
+        api_key: impl Into<ApiKey>, address: String,
     ) -> Result<NaClient, NaApiError> {
-        let mut builder = ClientBuilder::new();
-        builder = builder.dns_resolver(Arc::new(HickoryResolver::default()));
-        let client = builder.build();
-        match client {
-            Ok(http_client) => Ok(Self {
-                api_key,
-                address,
-                http_client,
-            }),
-            Err(e) => Err(NaApiError::UnknownError(format!(
-                "Error building client: {:?}",
-                e
-            ))),
+        NaClientBuilder::new(api_key, address).build()
+    }
+
+    /// Start building a client with timeouts, proxy, resolver and so on
+    pub fn builder(
+        api_key: impl Into<ApiKey>, address: impl Into<String>,
+    ) -> NaClientBuilder {
+        NaClientBuilder::new(api_key, address)
+    }
+
+    /// Send the API key somewhere other than the query string
+    pub fn with_key_placement(mut self, key_placement: KeyPlacement) -> Self {
+        self.key_placement = key_placement;
+        self
+    }
+
+    /// Swap the retry policy, `RetryPolicy::none()` turns retrying off
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// The same client, also retrying POST, PUT, PATCH and DELETE, for a
+    /// call that is safe to repeat. Shares throttle and drift report, so
+    /// `Server::boot(&client.idempotent(), mbpkgid)` overrides the policy
+    /// for that one call
+    pub fn idempotent(&self) -> NaClient {
+        let mut client = self.clone();
+        client.retry.retry_mutations = true;
+        client
+    }
+
+    /// Compare responses with their structs, see `drift`. Starts a fresh
+    /// `drift_report`
+    pub fn with_schema_drift(mut self, schema_drift: SchemaDrift) -> Self {
+        self.drift = Arc::new(DriftTracker::new(schema_drift));
+        self
+    }
+
+    /// Unknown and missing fields seen so far, by endpoint. Always empty
+    /// with `SchemaDrift::Ignore`
+    pub fn drift_report(&self) -> DriftReport {
+        self.drift.report()
+    }
+
+    /// Scrub the API key from anything headed for an error or a log
+    pub fn redact(&self, text: &str) -> String {
+        self.api_key.redact(text)
+    }
+
+    /// Make a request for the client, retrying as the policy allows
+    async fn request(
+        &self, method: Method, path: &str, body: RequestBody,
+    ) -> Result<Vec<u8>, NaApiError> {
+        let mut attempt = 1;
+        loop {
+            match self.send(method.clone(), path, body.clone()).await {
+                Err(e) if self.retry.should_retry(&method, attempt, &e) => {
+                    let delay = self.retry.delay(attempt, &e);
+                    tracing::debug!(
+                        path,
+                        attempt,
+                        ?delay,
+                        error = %e,
+                        "retrying request"
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
         }
     }
 
-    /// Make a request for the client
-    async fn get(&self, path: &str) -> Result<Value, NaApiError> {
-        let api_key = if path.contains("?") {
-            format!("&key={}", &self.api_key)
-        } else {
-            format!("?key={}", &self.api_key)
+    /// Send a single request and hand back the body of a success
+    async fn send(
+        &self, method: Method, path: &str, body: RequestBody,
+    ) -> Result<Vec<u8>, NaApiError> {
+        let _permit = self.throttle.acquire().await;
+        tracing::debug!(%method, path, "sending request");
+        let url = match self.key_placement {
+            KeyPlacement::Query => Self::with_query(
+                &format!("{}{}", self.address, path),
+                &[("key", self.api_key.expose())],
+            )?,
+            _ => format!("{}{}", self.address, path),
         };
-        let result = self
-            .http_client
-            .get(format!("{}{}{}", self.address, path, api_key))
-            .send()
-            .await
-            .map_err(|e| {
-                NaApiError::UnknownError(format!(
-                    "Failed to finish request with error: {e}"
-                ))
+        let mut headers = HeaderMap::new();
+        let key_header = match &self.key_placement {
+            KeyPlacement::Query => None,
+            KeyPlacement::Header(name) => Some((
+                HeaderName::try_from(name.as_str()).map_err(|_| {
+                    NaApiError::APIKeyInvalid(format!(
+                        "{name} is not a valid header name"
+                    ))
+                })?,
+                self.api_key.expose().to_owned(),
+            )),
+            KeyPlacement::Bearer => Some((
+                AUTHORIZATION,
+                format!("Bearer {}", self.api_key.expose()),
+            )),
+        };
+        if let Some((name, value)) = key_header {
+            let mut value = HeaderValue::from_str(&value).map_err(|_| {
+                NaApiError::APIKeyInvalid(
+                    "API key can not be sent as a header".to_owned(),
+                )
             })?;
-        let result_json = result.json::<Value>().await.map_err(|e| {
-            NaApiError::UnknownError(format!(
-                "Failed to finish request with error: {e}"
-            ))
-        })?;
-        Ok(result_json)
+            value.set_sensitive(true);
+            headers.insert(name, value);
+        }
+        let response = self
+            .transport
+            .execute(HttpRequest {
+                method,
+                path: path.to_owned(),
+                url,
+                headers,
+                body,
+            })
+            .await?;
+        let status = response.status;
+        let retry_after = retry::retry_after(&response.headers);
+        let body = response.body;
+        tracing::debug!(path, status = status.as_u16(), "got response");
+        if !status.is_success() {
+            // error bodies usually still carry the API message
+            let message = serde_json::from_slice::<Value>(&body)
+                .ok()
+                .and_then(|v| v.get("message").map(value_to_string))
+                .map(|message| self.redact(&message));
+            return Err(NaApiError::HttpStatus {
+                path: path.to_owned(),
+                status: status.as_u16(),
+                message,
+                retry_after,
+            });
+        }
+        Ok(body)
     }
 
-    /// Call the get and parse the results
-    /// We want to get the "data" attribute from the response for the calling
-    /// endpoint. Exit with error message if "data" is not present
-    /// This is shitty but it is safe enough so far as I can tell at this point
-    pub async fn get_data(&self, path: &str) -> Result<Value, NaApiError> {
-        // Get the response from get method
-        let result = self
-            .get(path)
+    /// Make a request and decode the "data" of the response as `T`, right
+    /// from the body bytes. `endpoint` names the type in errors and in the
+    /// drift report, `T` serializes back to be checked for drift
+    pub async fn fetch<T: DeserializeOwned + Serialize>(
+        &self, method: Method, endpoint: &str, path: &str, body: RequestBody,
+    ) -> Result<T, NaApiError> {
+        self.fetch_optional(method, endpoint, path, body)
+            .await?
+            .ok_or_else(|| no_data(path))
+    }
+
+    /// Like `fetch`, but a success that carries no "data", IE only a
+    /// message, is `None` rather than an error. Deletes and most other
+    /// changes answer like that
+    pub async fn fetch_optional<T: DeserializeOwned + Serialize>(
+        &self, method: Method, endpoint: &str, path: &str, body: RequestBody,
+    ) -> Result<Option<T>, NaApiError> {
+        let body = self.request(method, path, body).await?;
+        let envelope = Envelope::from_slice(endpoint, path, &body)?;
+        let data = self.unwrap_data(path, envelope)?;
+        if let Some(data) = &data {
+            self.check_drift(endpoint, path, &body, data);
+        }
+        Ok(data)
+    }
+
+    /// GET `path` and decode its "data" as `T`
+    pub async fn get_as<T: DeserializeOwned + Serialize>(
+        &self, endpoint: &str, path: &str,
+    ) -> Result<T, NaApiError> {
+        self.fetch(Method::GET, endpoint, path, RequestBody::Empty)
             .await
-            .map_err(|e| NaApiError::UnknownError(format!("Got error: {e}")))?;
-
-        // Try to pull the "data" key from the response
-        let result_value: Option<&Value> = result.get("data");
-        if let Some(inner_data) = result_value {
-            Ok(inner_data.clone())
-        } else {
-            let result_message = result.get("message");
-            if let Some(message) = result_message {
-                if let Some(code) = result.get("code") {
-                    Err(NaApiError::APIKeyInvalid(format!("{code}: {message}")))
-                } else {
-                    Err(NaApiError::UnknownError(format!(
-                        "Could not reach: {}{}",
-                        self.api_key, path
-                    )))
-                }
-            } else {
-                Err(NaApiError::UnknownError(format!(
-                    "Could not reach: {}{}",
-                    self.api_key, path
-                )))
+    }
+
+    /// GET a list from `path`, following pages until the last one
+    pub async fn get_all_as<T: DeserializeOwned + Serialize>(
+        &self, endpoint: &str, path: &str,
+    ) -> Result<Vec<T>, NaApiError> {
+        let mut pager = Pager::new(path);
+        let mut items = Vec::new();
+        loop {
+            let path = pager.path().to_owned();
+            let body =
+                self.request(Method::GET, &path, RequestBody::Empty).await?;
+            let envelope: Envelope<Vec<T>> =
+                Envelope::from_slice(endpoint, &path, &body)?;
+            let (page, pages) = (envelope.page, envelope.pages);
+            let data = self
+                .unwrap_data(&path, envelope)?
+                .ok_or_else(|| no_data(&path))?;
+            self.check_drift(endpoint, &path, &body, &data);
+            items.extend(data);
+            if !pager.next(page, pages)? {
+                return Ok(items);
             }
         }
     }
+
+    /// Stream a list from the path `path` builds, see `stream`
+    pub fn stream_as<T, F>(
+        &self, endpoint: &'static str, path: F,
+    ) -> ItemStream<T>
+    where
+        T: DeserializeOwned + Serialize + Send + 'static,
+        F: FnOnce() -> Result<String, NaApiError>,
+    {
+        stream::items(self.clone(), endpoint, path())
+    }
+
+    /// POST `body` to `path` and decode the "data" as `T`
+    pub async fn post_as<T: DeserializeOwned + Serialize>(
+        &self, endpoint: &str, path: &str, body: RequestBody,
+    ) -> Result<T, NaApiError> {
+        self.fetch(Method::POST, endpoint, path, body).await
+    }
+
+    /// PUT `body` to `path` and decode the "data" as `T`
+    pub async fn put_as<T: DeserializeOwned + Serialize>(
+        &self, endpoint: &str, path: &str, body: RequestBody,
+    ) -> Result<T, NaApiError> {
+        self.fetch(Method::PUT, endpoint, path, body).await
+    }
+
+    /// Call the get and hand back the "data" attribute of the response
+    /// untyped, `get_as` skips the `Value` when the type is known
+    pub async fn get_data(&self, path: &str) -> Result<Value, NaApiError> {
+        self.get_as("response", path).await
+    }
+
+    /// Send a POST with the given body and parse the results, `null`
+    /// when the API only answered with a message
+    pub async fn post_data(
+        &self, path: &str, body: RequestBody,
+    ) -> Result<Value, NaApiError> {
+        self.change_data(Method::POST, path, body).await
+    }
+
+    /// Send a PUT with the given body and parse the results, `null`
+    /// when the API only answered with a message
+    pub async fn put_data(
+        &self, path: &str, body: RequestBody,
+    ) -> Result<Value, NaApiError> {
+        self.change_data(Method::PUT, path, body).await
+    }
+
+    /// Send a PATCH with the given body and parse the results, `null`
+    /// when the API only answered with a message
+    pub async fn patch_data(
+        &self, path: &str, body: RequestBody,
+    ) -> Result<Value, NaApiError> {
+        self.change_data(Method::PATCH, path, body).await
+    }
+
+    /// Send a DELETE with the given body and parse the results, `null`
+    /// when the API only answered with a message
+    pub async fn delete_data(
+        &self, path: &str, body: RequestBody,
+    ) -> Result<Value, NaApiError> {
+        self.change_data(Method::DELETE, path, body).await
+    }
+
+    async fn change_data(
+        &self, method: Method, path: &str, body: RequestBody,
+    ) -> Result<Value, NaApiError> {
+        let data = self.fetch_optional(method, "response", path, body).await?;
+        Ok(data.unwrap_or(Value::Null))
+    }
+
+    /// Append `query`, URL encoded, to `path`. `None` fields are left out
+    pub fn with_query<Q: Serialize + ?Sized>(
+        path: &str, query: &Q,
+    ) -> Result<String, NaApiError> {
+        let query = serde_urlencoded::to_string(query).map_err(|e| {
+            NaApiError::UnknownError(format!("Failed to encode query: {e}"))
+        })?;
+        Ok(match (query.is_empty(), path.contains('?')) {
+            (true, _) => path.to_owned(),
+            (false, true) => format!("{path}&{query}"),
+            (false, false) => format!("{path}?{query}"),
+        })
+    }
+
+    /// Compare the "data" in `body` with `kept`, what it was decoded into
+    fn check_drift<T: Serialize>(
+        &self, endpoint: &str, path: &str, body: &[u8], kept: &T,
+    ) {
+        if !self.drift.is_on() {
+            return;
+        }
+        if let Ok(Envelope {
+            data: Some(sent), ..
+        }) = serde_json::from_slice::<Envelope<Value>>(body)
+        {
+            self.drift.check(endpoint, path, &sent, kept);
+        }
+    }
+
+    /// Pull the "data" out of a response, or the API error sent instead.
+    /// The HTTP status was a success already, so only a `code` outside
+    /// 2xx makes it an error, a success without data is `None`
+    fn unwrap_data<T>(
+        &self, path: &str, envelope: Envelope<T>,
+    ) -> Result<Option<T>, NaApiError> {
+        let code = envelope.code.as_ref().and_then(value_to_code);
+        match envelope {
+            Envelope {
+                data: Some(data), ..
+            } => Ok(Some(data)),
+            Envelope { message, .. }
+                if code.is_some_and(|code| !(200..300).contains(&code)) =>
+            {
+                Err(NaApiError::Api {
+                    path: path.to_owned(),
+                    code: code.unwrap_or(0),
+                    message: message
+                        .map(|message| self.redact(&value_to_string(&message)))
+                        .unwrap_or_default(),
+                })
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+fn no_data(path: &str) -> NaApiError {
+    NaApiError::UnknownError(format!("Response from {path} has no data"))
+}
+
+/// Render a serde path as a JSON pointer (RFC 6901)
+fn json_pointer(path: &serde_path_to_error::Path) -> String {
+    use serde_path_to_error::Segment;
+    let mut pointer = String::new();
+    for segment in path.iter() {
+        match segment {
+            Segment::Seq { index } => pointer.push_str(&format!("/{index}")),
+            Segment::Map { key } => {
+                pointer.push('/');
+                pointer.push_str(&key.replace('~', "~0").replace('/', "~1"));
+            }
+            Segment::Enum { variant } => {
+                pointer.push('/');
+                pointer.push_str(variant);
+            }
+            Segment::Unknown => pointer.push_str("/?"),
+        }
+    }
+    pointer
+}
+
+/// The API sends messages as plain strings, but be kind to anything else
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.to_owned(),
+        other => other.to_string(),
+    }
+}
+
+/// The API sends `code` both as a number and as a numeric string
+fn value_to_code(value: &Value) -> Option<i64> {
+    match value {
+        Value::Number(n) => n.as_i64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
 }
@@ -0,0 +1,135 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+
+/// Client side request rate, enforced with a token bucket
+///
+/// `requests` tokens are added every `per`, at most `burst` can be saved
+/// up, and every request (retries included) spends one
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    pub requests: u32,
+    pub per: Duration,
+    pub burst: u32,
+}
+
+impl RateLimit {
+    pub fn per_second(requests: u32) -> Self {
+        RateLimit {
+            requests,
+            per: Duration::from_secs(1),
+            burst: requests,
+        }
+    }
+
+    pub fn per_minute(requests: u32) -> Self {
+        RateLimit {
+            requests,
+            per: Duration::from_secs(60),
+            burst: requests,
+        }
+    }
+
+    /// How many requests can go out back to back after being idle
+    pub fn burst(mut self, burst: u32) -> Self {
+        self.burst = burst;
+        self
+    }
+
+    /// tokens per second
+    fn rate(&self) -> f64 {
+        f64::from(self.requests.max(1)) / self.per.as_secs_f64().max(1e-9)
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    limit: RateLimit,
+    tokens: f64,
+    last: Instant,
+}
+
+impl TokenBucket {
+    /// Take a token, or say how long until there is one
+    fn take(&mut self) -> Result<(), Duration> {
+        let now = Instant::now();
+        let capacity = f64::from(self.limit.burst.max(1));
+        let elapsed = now.duration_since(self.last).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.limit.rate()).min(capacity);
+        self.last = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - self.tokens) / self.limit.rate(),
+            ))
+        }
+    }
+}
+
+/// Rate limit and concurrency cap for one `NaClient` and all of its clones
+#[derive(Debug, Default)]
+pub(crate) struct Throttle {
+    bucket: Option<Mutex<TokenBucket>>,
+    permits: Option<Arc<Semaphore>>,
+    max_concurrency: usize,
+}
+
+impl Throttle {
+    pub(crate) fn new(
+        rate_limit: Option<RateLimit>, max_concurrency: Option<usize>,
+    ) -> Self {
+        Throttle {
+            bucket: rate_limit.map(|limit| {
+                Mutex::new(TokenBucket {
+                    limit,
+                    tokens: f64::from(limit.burst.max(1)),
+                    last: Instant::now(),
+                })
+            }),
+            permits: max_concurrency
+                .map(|n| Arc::new(Semaphore::new(n.max(1)))),
+            max_concurrency: max_concurrency.unwrap_or(0),
+        }
+    }
+
+    pub(crate) fn rate_limit(&self) -> Option<RateLimit> {
+        self.bucket.as_ref().map(|bucket| {
+            bucket.lock().unwrap_or_else(|e| e.into_inner()).limit
+        })
+    }
+
+    pub(crate) fn max_concurrency(&self) -> Option<usize> {
+        self.permits.as_ref().map(|_| self.max_concurrency)
+    }
+
+    /// Wait for a concurrency slot and a token, hold on to the returned
+    /// permit until the request is done
+    pub(crate) async fn acquire(&self) -> Option<OwnedSemaphorePermit> {
+        let permit = match &self.permits {
+            // the semaphore is never closed
+            Some(permits) => permits.clone().acquire_owned().await.ok(),
+            None => None,
+        };
+        if let Some(bucket) = &self.bucket {
+            loop {
+                let wait = {
+                    let mut bucket =
+                        bucket.lock().unwrap_or_else(|e| e.into_inner());
+                    match bucket.take() {
+                        Ok(()) => break,
+                        Err(wait) => wait,
+                    }
+                };
+                tracing::trace!(?wait, "rate limited, waiting for a token");
+                tokio::time::sleep(wait).await;
+            }
+        }
+        permit
+    }
+}
@@ -0,0 +1,214 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! Network types for the address fields the API sends as strings
+//!
+//! Addresses decode to `std::net` types, IPv4 netmasks (`255.255.255.0`)
+//! to `Netmask`, IPv6 prefix lengths (`"64"`) to `PrefixLen` and NIC
+//! addresses to `MacAddr`. A value that does not parse fails the
+//! response with the field it came from, it is never passed on as is
+//!
+//! ```rust
+//! use rnaapi::net::{MacAddr, Netmask};
+//!
+//! let mask: Netmask = "255.255.255.0".parse().unwrap();
+//! assert_eq!(mask.prefix_len(), 24);
+//! assert!("255.0.255.0".parse::<Netmask>().is_err());
+//!
+//! let mac: MacAddr = "52:54:00:AB:CD:01".parse().unwrap();
+//! assert_eq!(mac.to_string(), "52:54:00:ab:cd:01");
+//! ```
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+use std::fmt;
+use std::net::Ipv4Addr;
+use std::str::FromStr;
+
+/// A value that is not the address, mask or prefix it should be
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("invalid {kind} {value:?}")]
+pub struct NetParseError {
+    kind: &'static str,
+    value: String,
+}
+
+impl NetParseError {
+    fn new(kind: &'static str, value: &str) -> Self {
+        NetParseError {
+            kind,
+            value: value.to_owned(),
+        }
+    }
+}
+
+/// Strings both ways, through `FromStr` and `Display`
+macro_rules! string_serde {
+    ($name:ident) => {
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(
+                &self, serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<Self, D::Error> {
+                let text = String::deserialize(deserializer)?;
+                text.parse().map_err(de::Error::custom)
+            }
+        }
+    };
+}
+
+/// An IPv4 netmask, sent dotted. Only contiguous masks are accepted
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Netmask(u8);
+
+impl Netmask {
+    /// `None` past /32
+    pub const fn new(prefix_len: u8) -> Option<Self> {
+        if prefix_len <= 32 {
+            Some(Netmask(prefix_len))
+        } else {
+            None
+        }
+    }
+
+    pub const fn prefix_len(self) -> u8 {
+        self.0
+    }
+
+    /// The mask as an address, IE `255.255.255.0`
+    pub fn addr(self) -> Ipv4Addr {
+        let bits = u32::MAX.checked_shl(32 - u32::from(self.0)).unwrap_or(0);
+        Ipv4Addr::from(bits)
+    }
+}
+
+impl FromStr for Netmask {
+    type Err = NetParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || NetParseError::new("netmask", s);
+        let addr: Ipv4Addr = s.trim().parse().map_err(|_| error())?;
+        ipnet::ipv4_mask_to_prefix(addr)
+            .map(Netmask)
+            .map_err(|_| error())
+    }
+}
+
+impl fmt::Display for Netmask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.addr().fmt(f)
+    }
+}
+
+string_serde!(Netmask);
+
+/// An IPv6 prefix length, sent as a string like `"64"`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PrefixLen(u8);
+
+impl PrefixLen {
+    /// `None` past /128
+    pub const fn new(prefix_len: u8) -> Option<Self> {
+        if prefix_len <= 128 {
+            Some(PrefixLen(prefix_len))
+        } else {
+            None
+        }
+    }
+
+    pub const fn get(self) -> u8 {
+        self.0
+    }
+}
+
+impl FromStr for PrefixLen {
+    type Err = NetParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim().trim_start_matches('/');
+        text.parse()
+            .ok()
+            .and_then(PrefixLen::new)
+            .ok_or_else(|| NetParseError::new("prefix length", s))
+    }
+}
+
+impl fmt::Display for PrefixLen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+string_serde!(PrefixLen);
+
+/// A NIC's hardware address, sent as `52:54:00:ab:cd:01`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MacAddr(pub [u8; 6]);
+
+impl FromStr for MacAddr {
+    type Err = NetParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || NetParseError::new("MAC address", s);
+        let separator = if s.contains('-') { '-' } else { ':' };
+        let mut octets = [0u8; 6];
+        let mut parts = s.trim().split(separator);
+        for octet in octets.iter_mut() {
+            let part =
+                parts.next().filter(|p| p.len() == 2).ok_or_else(error)?;
+            *octet = u8::from_str_radix(part, 16).map_err(|_| error())?;
+        }
+        match parts.next() {
+            Some(_) => Err(error()),
+            None => Ok(MacAddr(octets)),
+        }
+    }
+}
+
+impl fmt::Display for MacAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c, d, e, g] = self.0;
+        write!(f, "{a:02x}:{b:02x}:{c:02x}:{d:02x}:{e:02x}:{g:02x}")
+    }
+}
+
+string_serde!(MacAddr);
+
+/// `Option` of an address, `""` and `null` are `None`
+pub mod optional {
+    use serde::{Deserialize, Deserializer, Serializer, de};
+    use std::fmt::Display;
+    use std::str::FromStr;
+
+    pub fn serialize<T: Display, S: Serializer>(
+        value: &Option<T>, serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => serializer.collect_str(value),
+            None => serializer.serialize_str(""),
+        }
+    }
+
+    pub fn deserialize<'de, T, D>(
+        deserializer: D,
+    ) -> Result<Option<T>, D::Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+        D: Deserializer<'de>,
+    {
+        let text = Option::<String>::deserialize(deserializer)?;
+        match text.as_deref().map(str::trim) {
+            None | Some("") => Ok(None),
+            Some(text) => text
+                .parse()
+                .map(Some)
+                .map_err(|e| de::Error::custom(format!("{text:?}: {e}"))),
+        }
+    }
+}
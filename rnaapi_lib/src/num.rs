@@ -0,0 +1,222 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! Numbers the API sends sometimes as JSON numbers, sometimes as strings
+//!
+//! `num_or_string` takes either for any `FromStr` number, `Money` keeps
+//! prices and balances exact instead of going through a float
+//!
+//! ```rust
+//! use rnaapi::num::{Money, num_or_string};
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Plan {
+//!     #[serde(with = "num_or_string")]
+//!     ram: u32,
+//!     price: Money,
+//! }
+//!
+//! let a: Plan = serde_json::from_str(r#"{"ram": "1024", "price": "5.00"}"#).unwrap();
+//! let b: Plan = serde_json::from_str(r#"{"ram": 1024, "price": 5}"#).unwrap();
+//! assert_eq!((a.ram, a.price), (b.ram, b.price));
+//! assert_eq!(a.price.to_string(), "5.00");
+//! ```
+use rust_decimal::Decimal;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+
+/// Whatever was sent, as text, from a number or a string
+struct Text;
+
+impl<'de> de::Visitor<'de> for Text {
+    type Value = String;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a number or a numeric string")
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<String, E> {
+        Ok(v.to_string())
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<String, E> {
+        Ok(v.to_string())
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<String, E> {
+        Ok(v.to_string())
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<String, E> {
+        Ok(v.trim().to_owned())
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<String, E> {
+        Ok(String::new())
+    }
+}
+
+/// Parse the text of a number or string into `T`, `None` when empty
+fn parse<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+    D: Deserializer<'de>,
+{
+    let text = deserializer.deserialize_any(Text)?;
+    if text.is_empty() {
+        return Ok(None);
+    }
+    text.parse()
+        .map(Some)
+        .map_err(|e| de::Error::custom(format!("invalid number {text:?}: {e}")))
+}
+
+/// Like `parse`, but a value has to be there
+fn parse_required<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+    D: Deserializer<'de>,
+{
+    parse(deserializer)?
+        .ok_or_else(|| de::Error::custom("expected a number, got nothing"))
+}
+
+/// A number sent as `1024` or as `"1024"`, written back as a number
+pub mod num_or_string {
+    use serde::{Deserializer, Serialize, Serializer};
+    use std::fmt::Display;
+    use std::str::FromStr;
+
+    pub fn serialize<T: Serialize, S: Serializer>(
+        value: &T, serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+        D: Deserializer<'de>,
+    {
+        super::parse_required(deserializer)
+    }
+
+    /// Same for an `Option`, `null` and `""` are `None`
+    pub mod option {
+        use serde::{Deserializer, Serialize, Serializer};
+        use std::fmt::Display;
+        use std::str::FromStr;
+
+        pub fn serialize<T: Serialize, S: Serializer>(
+            value: &Option<T>, serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            value.serialize(serializer)
+        }
+
+        pub fn deserialize<'de, T, D>(
+            deserializer: D,
+        ) -> Result<Option<T>, D::Error>
+        where
+            T: FromStr,
+            T::Err: Display,
+            D: Deserializer<'de>,
+        {
+            super::super::parse(deserializer)
+        }
+    }
+}
+
+/// An exact amount of money, sent as `"40.00"` or as a number
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Money(Decimal);
+
+impl Money {
+    pub const ZERO: Money = Money(Decimal::ZERO);
+
+    pub fn new(amount: Decimal) -> Self {
+        Money(amount)
+    }
+
+    /// Whole cents, IE `Money::from_cents(4000)` is `40.00`
+    pub fn from_cents(cents: i64) -> Self {
+        Money(Decimal::new(cents, 2))
+    }
+
+    pub fn amount(self) -> Decimal {
+        self.0
+    }
+}
+
+impl FromStr for Money {
+    type Err = rust_decimal::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Decimal::from_str_exact(s.trim()).map(Money)
+    }
+}
+
+/// Two places, the way the API writes amounts. Rounds, see `Serialize`
+/// for the exact amount
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.2}", self.0)
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, other: Money) -> Money {
+        Money(self.0 + other.0)
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, other: Money) -> Money {
+        Money(self.0 - other.0)
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.fold(Money::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Money> for Money {
+    fn sum<I: Iterator<Item = &'a Money>>(iter: I) -> Money {
+        iter.copied().sum()
+    }
+}
+
+/// At least two places like the API, but never fewer digits than there
+/// are, `"0.0125"` stays `"0.0125"`
+impl Serialize for Money {
+    fn serialize<S: Serializer>(
+        &self, serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut amount = self.0;
+        if amount.scale() < 2 {
+            // only adds zeros
+            amount.rescale(2);
+        }
+        serializer.collect_str(&amount)
+    }
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        parse_required(deserializer)
+    }
+}
@@ -0,0 +1,140 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+use rand::Rng;
+use reqwest::Method;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use std::time::Duration;
+
+use crate::errors::NaApiError;
+
+/// When and how often a failed request is sent again
+///
+/// Transport failures, 429s and 5xx responses are retried with exponential
+/// backoff plus jitter. Only GETs are retried unless `retry_mutations` is
+/// set, a POST that timed out may well have gone through, see
+/// `NaClient::idempotent` to allow it for a single call. No wait is ever
+/// longer than `max_delay`, a `Retry-After` asking for more ends the retries
+///
+/// Only the HTTP status is looked at. A 2xx whose body has a 429 `code` is
+/// not retried, that body is decoded after the retries are done, and the
+/// `NaApiError::Api` it ends in says `is_rate_limited` for the caller
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total tries including the first one, 1 turns retrying off
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled for every retry after that
+    pub base_delay: Duration,
+    /// Upper bound for the backoff delay
+    pub max_delay: Duration,
+    /// Wait as long as a `Retry-After` header says, instead of the backoff,
+    /// as long as that is within `max_delay`
+    pub honor_retry_after: bool,
+    /// Also retry POST, PUT, PATCH and DELETE
+    pub retry_mutations: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(10),
+            honor_retry_after: true,
+            retry_mutations: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Send every request exactly once
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    pub fn honor_retry_after(mut self, honor_retry_after: bool) -> Self {
+        self.honor_retry_after = honor_retry_after;
+        self
+    }
+
+    pub fn retry_mutations(mut self, retry_mutations: bool) -> Self {
+        self.retry_mutations = retry_mutations;
+        self
+    }
+
+    /// Is another try worth it after `attempt` tries failed with `error`
+    pub fn should_retry(
+        &self, method: &Method, attempt: u32, error: &NaApiError,
+    ) -> bool {
+        if attempt >= self.max_attempts {
+            return false;
+        }
+        if *method != Method::GET && !self.retry_mutations {
+            return false;
+        }
+        if let Some(retry_after) = self.retry_after(error)
+            && retry_after > self.max_delay
+        {
+            // not worth waiting for, let the caller decide
+            return false;
+        }
+        error.is_transport()
+            || error.is_rate_limited()
+            || error.is_server_error()
+    }
+
+    /// How long to wait after `attempt` tries failed with `error`
+    pub fn delay(&self, attempt: u32, error: &NaApiError) -> Duration {
+        if let Some(retry_after) = self.retry_after(error) {
+            return retry_after.min(self.max_delay);
+        }
+        // exponential backoff, half fixed and half random (equal jitter)
+        let exp = attempt.saturating_sub(1).min(16);
+        let backoff =
+            self.base_delay.saturating_mul(1 << exp).min(self.max_delay);
+        let half = backoff / 2;
+        let jitter = rand::rng().random_range(0..=half.as_millis() as u64);
+        half + Duration::from_millis(jitter)
+    }
+
+    /// The wait `error` asked for, when it is to be honored
+    fn retry_after(&self, error: &NaApiError) -> Option<Duration> {
+        match error {
+            NaApiError::HttpStatus { retry_after, .. }
+                if self.honor_retry_after =>
+            {
+                *retry_after
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Parse a `Retry-After` header, either delay seconds or an HTTP date
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let wait = date.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    Some(wait.to_std().unwrap_or(Duration::ZERO))
+}
@@ -0,0 +1,315 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+use chrono::{NaiveDate, NaiveDateTime};
+use std::time::{Duration, Instant};
+
+use super::SimState;
+use crate::endpoints::SrvJob;
+use crate::ids::{JobId, LocationId, MbPkgId, OsId, PlanId};
+use crate::status::{JobStatus, ServerState};
+
+/// Simulated time, only moves when advanced or, with a speed set, along
+/// with the wall clock
+#[derive(Clone, Debug)]
+pub struct VirtualClock {
+    epoch: NaiveDateTime,
+    offset: Duration,
+    speed: f64,
+    started: Instant,
+}
+
+impl Default for VirtualClock {
+    fn default() -> Self {
+        let epoch = NaiveDate::from_ymd_opt(2025, 10, 14)
+            .and_then(|d| d.and_hms_opt(12, 0, 0))
+            .expect("valid epoch");
+        VirtualClock::new(epoch)
+    }
+}
+
+impl VirtualClock {
+    pub fn new(epoch: NaiveDateTime) -> Self {
+        VirtualClock {
+            epoch,
+            offset: Duration::ZERO,
+            speed: 0.0,
+            started: Instant::now(),
+        }
+    }
+
+    /// Virtual seconds that pass per wall clock second, 0 stops the clock
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        self.speed = speed.max(0.0);
+        self
+    }
+
+    /// Time since the epoch
+    pub fn elapsed(&self) -> Duration {
+        self.offset + self.started.elapsed().mul_f64(self.speed)
+    }
+
+    pub fn now(&self) -> NaiveDateTime {
+        self.epoch + self.elapsed()
+    }
+
+    pub fn advance(&mut self, by: Duration) {
+        self.offset += by;
+    }
+}
+
+/// How long jobs sit in each phase, in virtual time
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct JobTiming {
+    pub queued: Duration,
+    pub running: Duration,
+    /// builds and rebuilds run this long instead of `running`
+    pub building: Duration,
+}
+
+impl Default for JobTiming {
+    fn default() -> Self {
+        JobTiming {
+            queued: Duration::from_secs(5),
+            running: Duration::from_secs(30),
+            building: Duration::from_secs(180),
+        }
+    }
+}
+
+impl JobTiming {
+    /// Every job is done the moment it is submitted
+    pub fn instant() -> Self {
+        JobTiming {
+            queued: Duration::ZERO,
+            running: Duration::ZERO,
+            building: Duration::ZERO,
+        }
+    }
+}
+
+/// Things that can be done to a server, each one runs as a job
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Build,
+    Rebuild,
+    Boot,
+    Shutdown,
+    Reboot,
+}
+
+impl Action {
+    /// `SrvJob.command`
+    pub fn command(self) -> &'static str {
+        match self {
+            Action::Build => "build",
+            Action::Rebuild => "rebuild",
+            Action::Boot => "boot",
+            Action::Shutdown => "shutdown",
+            Action::Reboot => "reboot",
+        }
+    }
+
+    /// `Server.state` while the job is running
+    fn busy_state(self) -> ServerState {
+        match self {
+            Action::Build | Action::Rebuild => ServerState::Building,
+            Action::Boot => ServerState::Booting,
+            Action::Shutdown => ServerState::ShuttingDown,
+            Action::Reboot => ServerState::Rebooting,
+        }
+    }
+
+    /// `Server.state` once the job is done
+    fn final_state(self) -> ServerState {
+        match self {
+            Action::Shutdown => ServerState::Stopped,
+            _ => ServerState::Running,
+        }
+    }
+
+    fn is_build(self) -> bool {
+        matches!(self, Action::Build | Action::Rebuild)
+    }
+}
+
+/// Changes a finished build makes to the server
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BuildSpec {
+    pub fqdn: Option<String>,
+    pub location_id: Option<LocationId>,
+    pub plan_id: Option<PlanId>,
+    pub os_id: Option<OsId>,
+}
+
+#[derive(Clone, Debug)]
+struct PendingJob {
+    mbpkgid: MbPkgId,
+    id: JobId,
+    action: Action,
+    submitted: Duration,
+    fail: bool,
+    build: BuildSpec,
+    /// state to go back to when the job fails
+    prior_state: ServerState,
+}
+
+/// Runs server jobs through queued, running and done or failed
+#[derive(Debug, Default)]
+pub(crate) struct JobRunner {
+    pub(crate) clock: VirtualClock,
+    pub(crate) timing: JobTiming,
+    pending: Vec<PendingJob>,
+    fail_next: Vec<Action>,
+}
+
+impl JobRunner {
+    pub(crate) fn new(clock: VirtualClock, timing: JobTiming) -> Self {
+        JobRunner {
+            clock,
+            timing,
+            pending: Vec::new(),
+            fail_next: Vec::new(),
+        }
+    }
+
+    /// Make the next job for `action` fail
+    pub(crate) fn fail_next(&mut self, action: Action) {
+        self.fail_next.push(action);
+    }
+
+    pub(crate) fn is_busy(&self, mbpkgid: MbPkgId) -> bool {
+        self.pending.iter().any(|job| job.mbpkgid == mbpkgid)
+    }
+
+    /// Queue a job, the caller made sure the server exists and is idle
+    pub(crate) fn submit(
+        &mut self, state: &mut SimState, mbpkgid: MbPkgId, action: Action,
+        build: BuildSpec,
+    ) -> SrvJob {
+        let id = state
+            .jobs
+            .values()
+            .flatten()
+            .map(|job| job.id.get())
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let id = JobId(id);
+        let job = SrvJob {
+            id,
+            ts_insert: Some(self.clock.now()),
+            command: action.command().to_owned(),
+            status: JobStatus::Queued,
+            mbpkgid: action.is_build().then_some(mbpkgid),
+        };
+        state.jobs.entry(mbpkgid).or_default().push(job.clone());
+        let fail = match self.fail_next.iter().position(|a| *a == action) {
+            Some(index) => {
+                self.fail_next.remove(index);
+                true
+            }
+            None => false,
+        };
+        let prior_state = state
+            .servers
+            .iter()
+            .find(|s| s.mbpkgid == mbpkgid)
+            .map(|s| s.state.clone())
+            .unwrap_or(ServerState::Stopped);
+        self.pending.push(PendingJob {
+            mbpkgid,
+            id,
+            action,
+            submitted: self.clock.elapsed(),
+            fail,
+            build,
+            prior_state,
+        });
+        self.settle(state);
+        // hand back the job as it stands after settling
+        state
+            .jobs
+            .get(&mbpkgid)
+            .and_then(|jobs| jobs.iter().find(|j| j.id == id))
+            .cloned()
+            .unwrap_or(job)
+    }
+
+    /// Move every pending job along to where the clock says it should be
+    pub(crate) fn settle(&mut self, state: &mut SimState) {
+        let now = self.clock.elapsed();
+        let timing = self.timing;
+        self.pending.retain(|job| {
+            let age = now.saturating_sub(job.submitted);
+            let running = if job.action.is_build() {
+                timing.building
+            } else {
+                timing.running
+            };
+            let status = if age < timing.queued {
+                JobStatus::Queued
+            } else if age < timing.queued + running {
+                JobStatus::Running
+            } else if job.fail {
+                JobStatus::Failed
+            } else {
+                JobStatus::Done
+            };
+            apply(state, job, status);
+            !status.is_terminal()
+        });
+    }
+}
+
+/// Reflect a job's status on the job itself, the server and its package
+fn apply(state: &mut SimState, job: &PendingJob, status: JobStatus) {
+    if let Some(entry) = state
+        .jobs
+        .get_mut(&job.mbpkgid)
+        .and_then(|jobs| jobs.iter_mut().find(|j| j.id == job.id))
+    {
+        entry.status = status;
+    }
+    let server_state = match status {
+        JobStatus::Queued => return,
+        JobStatus::Running => job.action.busy_state(),
+        JobStatus::Done => job.action.final_state(),
+        _ => job.prior_state.clone(),
+    };
+    let building = status.is_running() && job.action.is_build();
+    if let Some(server) =
+        state.servers.iter_mut().find(|s| s.mbpkgid == job.mbpkgid)
+    {
+        server.state = server_state.clone();
+        if job.action.is_build() && !status.is_failed() {
+            server.installed = u32::from(status == JobStatus::Done);
+        }
+        if status == JobStatus::Done {
+            let build = &job.build;
+            if let Some(fqdn) = &build.fqdn {
+                server.fqdn = fqdn.clone();
+            }
+            if let Some(location_id) = build.location_id {
+                server.location_id = location_id;
+            }
+            if let Some(plan_id) = build.plan_id {
+                server.plan_id = plan_id;
+            }
+            if let Some(os_id) = build.os_id {
+                server.os_id = os_id;
+            }
+        }
+    }
+    if let Some(package) =
+        state.packages.iter_mut().find(|p| p.mbpkgid == job.mbpkgid)
+    {
+        package.state = server_state;
+        package.is_building = u32::from(building);
+        if status == JobStatus::Done
+            && let Some(fqdn) = &job.build.fqdn
+        {
+            package.fqdn = fqdn.clone();
+        }
+    }
+}
@@ -0,0 +1,226 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! Local stand in for the NetActuate API
+//!
+//! Serves the `account/*`, `cloud/*` and `dns/*` routes the endpoint
+//! structs use, with the same `data` envelope and `code`/`message`
+//! errors, from state seeded out of fixture files. Point `API_ADDRESS`
+//! at it to run rnatui or your own tools without a real account
+//!
+//! Build, boot, shutdown, reboot and rebuild calls create `SrvJob`s. By
+//! default they are done at once, in stateful mode they go through
+//! queued and running on a virtual clock, changing `Server.state` (and
+//! with it `SrvStatus`) as they go, and end up done or failed
+//!
+//! ```rust,no_run
+//! use rnaapi::NaClient;
+//! use rnaapi::sim::{SimState, Simulator};
+//!
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! let sim = Simulator::new(SimState::default_seed()).with_api_key("test");
+//! let running = sim.spawn().await?;
+//! let client = NaClient::new("test", running.address()).await?;
+//! # Ok(())
+//! # }
+//! ```
+use chrono::NaiveDateTime;
+use std::net::SocketAddr;
+use std::ops::Deref;
+use std::sync::{
+    Arc, Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard,
+};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+mod jobs;
+mod routes;
+mod state;
+
+use jobs::JobRunner;
+pub use jobs::{Action, BuildSpec, JobTiming, VirtualClock};
+pub use state::{DEFAULT_SEED, SimState};
+
+/// Key the simulator accepts unless told otherwise
+pub const DEFAULT_API_KEY: &str = "sim-api-key";
+
+/// Invoices and DNS records past this many come back in pages
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+
+#[derive(Debug)]
+pub(crate) struct SimInner {
+    state: RwLock<SimState>,
+    jobs: Mutex<JobRunner>,
+    api_key: String,
+    page_size: usize,
+}
+
+/// Shared handle the route handlers work on
+#[derive(Clone, Debug)]
+pub(crate) struct Sim(Arc<SimInner>);
+
+impl Deref for Sim {
+    type Target = SimInner;
+
+    fn deref(&self) -> &SimInner {
+        &self.0
+    }
+}
+
+impl SimInner {
+    pub(crate) fn read(&self) -> RwLockReadGuard<'_, SimState> {
+        self.state.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub(crate) fn write(&self) -> RwLockWriteGuard<'_, SimState> {
+        self.state.write().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Always taken after the state lock, never before
+    pub(crate) fn jobs(&self) -> MutexGuard<'_, JobRunner> {
+        self.jobs.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Catch the state up with the virtual clock
+    pub(crate) fn settle(&self) {
+        let mut state = self.write();
+        self.jobs().settle(&mut state);
+    }
+}
+
+/// The simulated API, build one, then `serve` or `spawn` it
+#[derive(Debug)]
+pub struct Simulator {
+    state: SimState,
+    api_key: String,
+    clock: VirtualClock,
+    timing: JobTiming,
+    page_size: usize,
+}
+
+impl Simulator {
+    pub fn new(state: SimState) -> Self {
+        Simulator {
+            state,
+            api_key: DEFAULT_API_KEY.to_owned(),
+            clock: VirtualClock::default(),
+            timing: JobTiming::instant(),
+            page_size: DEFAULT_PAGE_SIZE,
+        }
+    }
+
+    /// Only accept this key
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = api_key.into();
+        self
+    }
+
+    /// Run jobs over (virtual) time instead of finishing them at once
+    pub fn stateful(mut self, timing: JobTiming) -> Self {
+        self.timing = timing;
+        self
+    }
+
+    /// Start the virtual clock at `epoch`
+    pub fn with_epoch(mut self, epoch: NaiveDateTime) -> Self {
+        self.clock = VirtualClock::new(epoch);
+        self
+    }
+
+    /// Let the virtual clock run `speed` times as fast as the wall clock,
+    /// by default it only moves with `advance`
+    pub fn with_clock_speed(mut self, speed: f64) -> Self {
+        self.clock = self.clock.with_speed(speed);
+        self
+    }
+
+    /// Page invoice and DNS record lists longer than `page_size`
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    fn into_sim(self) -> Sim {
+        Sim(Arc::new(SimInner {
+            state: RwLock::new(self.state),
+            jobs: Mutex::new(JobRunner::new(self.clock, self.timing)),
+            api_key: self.api_key,
+            page_size: self.page_size,
+        }))
+    }
+
+    /// The axum router, for mounting into something else
+    pub fn into_router(self) -> axum::Router {
+        routes::router(self.into_sim())
+    }
+
+    /// Serve on an already bound listener until the task is dropped
+    pub async fn serve(self, listener: TcpListener) -> std::io::Result<()> {
+        axum::serve(listener, self.into_router()).await
+    }
+
+    /// Serve on a random local port in the background
+    pub async fn spawn(self) -> std::io::Result<RunningSim> {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+        let local_addr = listener.local_addr()?;
+        let sim = self.into_sim();
+        let router = routes::router(sim.clone());
+        let handle =
+            tokio::spawn(async move { axum::serve(listener, router).await });
+        Ok(RunningSim {
+            local_addr,
+            sim,
+            handle,
+        })
+    }
+}
+
+/// A simulator serving in the background, stopped when dropped
+#[derive(Debug)]
+pub struct RunningSim {
+    local_addr: SocketAddr,
+    sim: Sim,
+    handle: JoinHandle<std::io::Result<()>>,
+}
+
+impl RunningSim {
+    /// Move the virtual clock forward, pending jobs catch up right away
+    pub fn advance(&self, by: Duration) {
+        let mut state = self.sim.write();
+        let mut jobs = self.sim.jobs();
+        jobs.clock.advance(by);
+        jobs.settle(&mut state);
+    }
+
+    /// Current virtual time
+    pub fn now(&self) -> NaiveDateTime {
+        self.sim.jobs().clock.now()
+    }
+
+    /// Make the next job for `action` end up failed
+    pub fn fail_next(&self, action: Action) {
+        self.sim.jobs().fail_next(action);
+    }
+
+    /// Copy of everything the simulator holds right now
+    pub fn snapshot(&self) -> SimState {
+        self.sim.settle();
+        self.sim.read().clone()
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Base address to hand to `NaClient`, IE `http://127.0.0.1:4321/api/`
+    pub fn address(&self) -> String {
+        format!("http://{}/api/", self.local_addr)
+    }
+}
+
+impl Drop for RunningSim {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
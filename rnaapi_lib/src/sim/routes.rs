@@ -0,0 +1,589 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+use axum::body::Bytes;
+use axum::extract::{Path, Query, Request, State};
+use axum::http::StatusCode;
+use axum::http::header::AUTHORIZATION;
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Form, Json, Router};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::fmt;
+
+use super::Sim;
+use super::jobs::{Action, BuildSpec};
+use crate::endpoints::{
+    Record, RecordRequest, SSHKeyRequest, SSHKeys, SrvStatus,
+};
+use crate::ids::{
+    JobId, LocationId, MbPkgId, OsId, PlanId, RecordId, SshKeyId, ZoneId,
+};
+use crate::status::ZoneType;
+
+/// Every route lives under `/api/`, like the real thing
+pub(super) fn router(sim: Sim) -> Router {
+    let api = Router::new()
+        .route("/account/details", get(details))
+        .route("/account/invoices", get(invoices))
+        .route("/account/ssh_keys", get(ssh_keys))
+        .route("/account/ssh_key", post(create_ssh_key))
+        .route("/account/ssh_key/{id}", get(ssh_key).delete(delete_ssh_key))
+        .route("/cloud/servers", get(servers))
+        .route("/cloud/server", get(server))
+        .route("/cloud/server/{mbpkgid}/jobs", get(jobs))
+        .route("/cloud/server/{mbpkgid}/jobs/{id}", get(job))
+        .route("/cloud/status/{mbpkgid}", get(status))
+        .route("/cloud/server/build", post(build_new))
+        .route("/cloud/server/build/{mbpkgid}", post(build))
+        .route("/cloud/server/rebuild/{mbpkgid}", post(rebuild))
+        .route("/cloud/server/start/{mbpkgid}", post(boot))
+        .route("/cloud/server/shutdown/{mbpkgid}", post(shutdown))
+        .route("/cloud/server/reboot/{mbpkgid}", post(reboot))
+        .route("/cloud/ipv4", get(ipv4))
+        .route("/cloud/ipv6", get(ipv6))
+        .route("/cloud/servermonthlybw/{mbpkgid}", get(bandwidth))
+        .route("/cloud/images", get(images))
+        .route("/cloud/locations", get(locations))
+        .route("/cloud/packages", get(packages))
+        .route("/cloud/sizes/{location}", get(sizes))
+        .route("/dns/zones", get(zones))
+        .route("/dns/zone/{id}", get(zone))
+        .route("/dns/records/{zone}", get(records))
+        .route("/dns/record", post(create_record))
+        .route(
+            "/dns/record/{id}",
+            get(record).put(update_record).delete(delete_record),
+        )
+        .route("/sim/advance", post(advance))
+        .route("/sim/fail", post(fail_next))
+        .fallback(not_found)
+        .layer(middleware::from_fn_with_state(sim.clone(), settle))
+        .layer(middleware::from_fn_with_state(sim.clone(), check_key));
+    Router::new().nest("/api", api).with_state(sim)
+}
+
+/// The `data` envelope every successful call is wrapped in
+pub(super) fn data<T: Serialize>(value: T) -> Response {
+    Json(json!({ "code": 200, "data": value })).into_response()
+}
+
+/// Lists longer than the page size go out a page at a time, with `page`
+/// and `pages` next to the `data`. Without `page=` the first one is sent
+pub(super) fn paged<T: Serialize>(
+    items: &[T], page: Option<u32>, page_size: usize,
+) -> Response {
+    if page.is_none() && items.len() <= page_size {
+        return data(items);
+    }
+    let pages = items.len().div_ceil(page_size).max(1);
+    let page = page.unwrap_or(1);
+    if page == 0 || page as usize > pages {
+        return error(StatusCode::NOT_FOUND, format!("No page {page}"));
+    }
+    let start = (page as usize - 1) * page_size;
+    let chunk = &items[start..items.len().min(start + page_size)];
+    let body =
+        json!({ "code": 200, "page": page, "pages": pages, "data": chunk });
+    Json(body).into_response()
+}
+
+/// The `code`/`message` envelope errors come back in
+pub(super) fn error(
+    status: StatusCode, message: impl Into<String>,
+) -> Response {
+    let body = json!({ "code": status.as_u16(), "message": message.into() });
+    (status, Json(body)).into_response()
+}
+
+fn missing(what: &str, id: impl fmt::Display) -> Response {
+    error(StatusCode::NOT_FOUND, format!("{what} {id} not found"))
+}
+
+async fn not_found() -> Response {
+    error(StatusCode::NOT_FOUND, "Unknown endpoint")
+}
+
+/// Accept the key as `?key=`, `Authorization: Bearer` or `X-API-Key`
+async fn check_key(
+    State(sim): State<Sim>, request: Request, next: Next,
+) -> Response {
+    let from_query = request.uri().query().and_then(query_key);
+    let from_header = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .or_else(|| {
+            request
+                .headers()
+                .get("x-api-key")
+                .and_then(|v| v.to_str().ok())
+        })
+        .map(str::to_owned);
+    match from_query.or(from_header) {
+        Some(key) if key == sim.api_key => next.run(request).await,
+        _ => error(StatusCode::UNAUTHORIZED, "Invalid API key"),
+    }
+}
+
+/// Bring jobs up to date before anything looks at the state
+async fn settle(
+    State(sim): State<Sim>, request: Request, next: Next,
+) -> Response {
+    sim.settle();
+    next.run(request).await
+}
+
+fn query_key(query: &str) -> Option<String> {
+    serde_urlencoded::from_str::<HashMap<String, String>>(query)
+        .ok()
+        .and_then(|mut pairs| pairs.remove("key"))
+}
+
+#[derive(Deserialize)]
+struct MbPkgQuery {
+    mbpkgid: MbPkgId,
+}
+
+#[derive(Deserialize)]
+struct PageQuery {
+    page: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct ZonesQuery {
+    #[serde(rename = "type")]
+    zone_type: Option<ZoneType>,
+}
+
+async fn details(State(sim): State<Sim>) -> Response {
+    match &sim.read().details {
+        Some(details) => data(details),
+        None => error(StatusCode::NOT_FOUND, "No account details"),
+    }
+}
+
+async fn invoices(
+    State(sim): State<Sim>, Query(query): Query<PageQuery>,
+) -> Response {
+    paged(&sim.read().invoices, query.page, sim.page_size)
+}
+
+async fn ssh_keys(State(sim): State<Sim>) -> Response {
+    data(&sim.read().ssh_keys)
+}
+
+async fn ssh_key(State(sim): State<Sim>, Path(id): Path<SshKeyId>) -> Response {
+    match sim.read().ssh_keys.iter().find(|k| k.id == id) {
+        Some(key) => data(key),
+        None => missing("SSH key", id),
+    }
+}
+
+async fn create_ssh_key(
+    State(sim): State<Sim>, Form(request): Form<SSHKeyRequest>,
+) -> Response {
+    let mut state = sim.write();
+    let id = state.ssh_keys.iter().map(|k| k.id.get()).max().unwrap_or(0) + 1;
+    let id = SshKeyId(id);
+    let key = SSHKeys {
+        id,
+        ssh_key: request.ssh_key,
+        fingerprint: format!("SHA256:sim{id}"),
+        name: request.name,
+    };
+    state.ssh_keys.push(key.clone());
+    data(key)
+}
+
+async fn delete_ssh_key(
+    State(sim): State<Sim>, Path(id): Path<SshKeyId>,
+) -> Response {
+    let mut state = sim.write();
+    let before = state.ssh_keys.len();
+    state.ssh_keys.retain(|k| k.id != id);
+    if state.ssh_keys.len() == before {
+        return missing("SSH key", id);
+    }
+    data(json!({ "deleted": id }))
+}
+
+async fn servers(State(sim): State<Sim>) -> Response {
+    data(&sim.read().servers)
+}
+
+async fn server(
+    State(sim): State<Sim>, Query(query): Query<MbPkgQuery>,
+) -> Response {
+    match sim
+        .read()
+        .servers
+        .iter()
+        .find(|s| s.mbpkgid == query.mbpkgid)
+    {
+        Some(server) => data(server),
+        None => missing("Server", query.mbpkgid),
+    }
+}
+
+async fn jobs(
+    State(sim): State<Sim>, Path(mbpkgid): Path<MbPkgId>,
+) -> Response {
+    let state = sim.read();
+    if !state.servers.iter().any(|s| s.mbpkgid == mbpkgid) {
+        return missing("Server", mbpkgid);
+    }
+    data(state.jobs.get(&mbpkgid).cloned().unwrap_or_default())
+}
+
+async fn job(
+    State(sim): State<Sim>, Path((mbpkgid, id)): Path<(MbPkgId, JobId)>,
+) -> Response {
+    let state = sim.read();
+    let job = state
+        .jobs
+        .get(&mbpkgid)
+        .and_then(|jobs| jobs.iter().find(|j| j.id == id));
+    match job {
+        Some(job) => data(job),
+        None => missing("Job", id),
+    }
+}
+
+async fn status(
+    State(sim): State<Sim>, Path(mbpkgid): Path<MbPkgId>,
+) -> Response {
+    match sim.read().servers.iter().find(|s| s.mbpkgid == mbpkgid) {
+        Some(server) => data(SrvStatus {
+            status: server.state.clone(),
+        }),
+        None => missing("Server", mbpkgid),
+    }
+}
+
+async fn ipv4(
+    State(sim): State<Sim>, Query(query): Query<MbPkgQuery>,
+) -> Response {
+    data(
+        sim.read()
+            .ipv4
+            .get(&query.mbpkgid)
+            .cloned()
+            .unwrap_or_default(),
+    )
+}
+
+async fn ipv6(
+    State(sim): State<Sim>, Query(query): Query<MbPkgQuery>,
+) -> Response {
+    data(
+        sim.read()
+            .ipv6
+            .get(&query.mbpkgid)
+            .cloned()
+            .unwrap_or_default(),
+    )
+}
+
+async fn bandwidth(
+    State(sim): State<Sim>, Path(mbpkgid): Path<MbPkgId>,
+) -> Response {
+    data(
+        sim.read()
+            .bandwidth
+            .get(&mbpkgid)
+            .cloned()
+            .unwrap_or_default(),
+    )
+}
+
+async fn images(State(sim): State<Sim>) -> Response {
+    data(&sim.read().images)
+}
+
+async fn locations(State(sim): State<Sim>) -> Response {
+    data(&sim.read().locations)
+}
+
+async fn packages(State(sim): State<Sim>) -> Response {
+    data(&sim.read().packages)
+}
+
+async fn sizes(
+    State(sim): State<Sim>, Path(location): Path<LocationId>,
+) -> Response {
+    match sim.read().sizes.get(&location) {
+        Some(sizes) => data(sizes),
+        None => missing("Location", location),
+    }
+}
+
+async fn zones(
+    State(sim): State<Sim>, Query(query): Query<ZonesQuery>,
+) -> Response {
+    let state = sim.read();
+    // the list only carries the zone itself, not its records
+    let zones: Vec<_> = state
+        .zones
+        .iter()
+        .filter(|z| query.zone_type.as_ref().is_none_or(|t| z.zone_type == *t))
+        .map(|z| {
+            let mut zone = z.clone();
+            zone.soa = None;
+            zone.records = None;
+            zone.ns = None;
+            zone
+        })
+        .collect();
+    data(zones)
+}
+
+async fn zone(State(sim): State<Sim>, Path(id): Path<ZoneId>) -> Response {
+    match sim.read().zones.iter().find(|z| z.id == id) {
+        Some(zone) => data(zone),
+        None => missing("Zone", id),
+    }
+}
+
+async fn records(
+    State(sim): State<Sim>, Path(zone): Path<ZoneId>,
+    Query(query): Query<PageQuery>,
+) -> Response {
+    match sim.read().zones.iter().find(|z| z.id == zone) {
+        Some(zone) => {
+            let records = zone.records.as_deref().unwrap_or_default();
+            paged(records, query.page, sim.page_size)
+        }
+        None => missing("Zone", zone),
+    }
+}
+
+async fn record(State(sim): State<Sim>, Path(id): Path<RecordId>) -> Response {
+    let state = sim.read();
+    let record = state
+        .zones
+        .iter()
+        .flat_map(|z| z.records.iter().flatten())
+        .find(|r| r.id == id);
+    match record {
+        Some(record) => data(record),
+        None => missing("Record", id),
+    }
+}
+
+fn record_from(id: RecordId, request: RecordRequest) -> Record {
+    Record {
+        id,
+        name: request.name,
+        record_type: request.record_type,
+        ttl: request.ttl,
+        prio: request.prio,
+        content: request.content,
+        domain_id: Some(request.domain_id),
+    }
+}
+
+async fn create_record(
+    State(sim): State<Sim>, Form(request): Form<RecordRequest>,
+) -> Response {
+    let mut state = sim.write();
+    let id = state
+        .zones
+        .iter()
+        .flat_map(|z| z.records.iter().flatten().chain(z.ns.iter().flatten()))
+        .map(|r| r.id.get())
+        .max()
+        .unwrap_or(0)
+        + 1;
+    let id = RecordId(id);
+    let domain_id = request.domain_id;
+    let Some(zone) = state.zones.iter_mut().find(|z| z.id == domain_id) else {
+        return missing("Zone", domain_id);
+    };
+    let record = record_from(id, request);
+    zone.records
+        .get_or_insert_with(Vec::new)
+        .push(record.clone());
+    data(record)
+}
+
+async fn update_record(
+    State(sim): State<Sim>, Path(id): Path<RecordId>,
+    Form(request): Form<RecordRequest>,
+) -> Response {
+    let mut state = sim.write();
+    let record = state
+        .zones
+        .iter_mut()
+        .flat_map(|z| z.records.iter_mut().flatten())
+        .find(|r| r.id == id);
+    match record {
+        Some(record) => {
+            *record = record_from(id, request);
+            data(record.clone())
+        }
+        None => missing("Record", id),
+    }
+}
+
+async fn delete_record(
+    State(sim): State<Sim>, Path(id): Path<RecordId>,
+) -> Response {
+    let mut state = sim.write();
+    for zone in state.zones.iter_mut() {
+        if let Some(records) = zone.records.as_mut()
+            && let Some(index) = records.iter().position(|r| r.id == id)
+        {
+            records.remove(index);
+            return data(json!({ "deleted": id }));
+        }
+    }
+    missing("Record", id)
+}
+
+#[derive(Deserialize, Default)]
+struct BuildForm {
+    fqdn: Option<String>,
+    hostname: Option<String>,
+    location_id: Option<LocationId>,
+    plan_id: Option<PlanId>,
+    os_id: Option<OsId>,
+}
+
+/// Submit a job for `action` unless the server is missing or busy
+fn submit(
+    sim: &Sim, mbpkgid: MbPkgId, action: Action, build: BuildSpec,
+) -> Response {
+    let mut state = sim.write();
+    if !state.servers.iter().any(|s| s.mbpkgid == mbpkgid) {
+        return missing("Server", mbpkgid);
+    }
+    let mut jobs = sim.jobs();
+    if jobs.is_busy(mbpkgid) {
+        return error(
+            StatusCode::CONFLICT,
+            format!("Server {mbpkgid} already has a job in progress"),
+        );
+    }
+    data(jobs.submit(&mut state, mbpkgid, action, build))
+}
+
+/// Build parameters are all optional, anything left out stays as it is
+fn build_spec(body: &[u8]) -> Result<BuildSpec, String> {
+    let form: BuildForm = if body.is_empty() {
+        BuildForm::default()
+    } else {
+        serde_urlencoded::from_bytes(body)
+            .map_err(|e| format!("Invalid build: {e}"))?
+    };
+    Ok(BuildSpec {
+        fqdn: form.fqdn.or(form.hostname),
+        location_id: form.location_id,
+        plan_id: form.plan_id,
+        os_id: form.os_id,
+    })
+}
+
+async fn build(
+    State(sim): State<Sim>, Path(mbpkgid): Path<MbPkgId>, body: Bytes,
+) -> Response {
+    match build_spec(&body) {
+        Ok(spec) => submit(&sim, mbpkgid, Action::Build, spec),
+        Err(message) => error(StatusCode::BAD_REQUEST, message),
+    }
+}
+
+/// Order a package and build it, the job tells which mbpkgid it got
+async fn build_new(State(sim): State<Sim>, body: Bytes) -> Response {
+    let spec = match build_spec(&body) {
+        Ok(spec) => spec,
+        Err(message) => return error(StatusCode::BAD_REQUEST, message),
+    };
+    if spec.fqdn.is_none() || spec.os_id.is_none() {
+        return error(
+            StatusCode::BAD_REQUEST,
+            "A new server needs a hostname, location_id, plan_id and os_id",
+        );
+    }
+    let ordered = sim.write().order(&spec);
+    match ordered {
+        Ok(mbpkgid) => submit(&sim, mbpkgid, Action::Build, spec),
+        Err(message) => error(StatusCode::BAD_REQUEST, message),
+    }
+}
+
+async fn rebuild(
+    State(sim): State<Sim>, Path(mbpkgid): Path<MbPkgId>, body: Bytes,
+) -> Response {
+    match build_spec(&body) {
+        Ok(spec) => submit(&sim, mbpkgid, Action::Rebuild, spec),
+        Err(message) => error(StatusCode::BAD_REQUEST, message),
+    }
+}
+
+async fn boot(
+    State(sim): State<Sim>, Path(mbpkgid): Path<MbPkgId>,
+) -> Response {
+    submit(&sim, mbpkgid, Action::Boot, BuildSpec::default())
+}
+
+async fn shutdown(
+    State(sim): State<Sim>, Path(mbpkgid): Path<MbPkgId>,
+) -> Response {
+    submit(&sim, mbpkgid, Action::Shutdown, BuildSpec::default())
+}
+
+async fn reboot(
+    State(sim): State<Sim>, Path(mbpkgid): Path<MbPkgId>,
+) -> Response {
+    submit(&sim, mbpkgid, Action::Reboot, BuildSpec::default())
+}
+
+#[derive(Deserialize)]
+struct AdvanceQuery {
+    seconds: u64,
+}
+
+/// Move the virtual clock forward, `sim/advance?seconds=60`
+async fn advance(
+    State(sim): State<Sim>, Query(query): Query<AdvanceQuery>,
+) -> Response {
+    let mut state = sim.write();
+    let mut jobs = sim.jobs();
+    jobs.clock
+        .advance(std::time::Duration::from_secs(query.seconds));
+    jobs.settle(&mut state);
+    data(
+        json!({ "now": jobs.clock.now().format("%Y-%m-%d %H:%M:%S").to_string() }),
+    )
+}
+
+#[derive(Deserialize)]
+struct FailQuery {
+    command: String,
+}
+
+/// Make the next job for a command fail, `sim/fail?command=boot`
+async fn fail_next(
+    State(sim): State<Sim>, Query(query): Query<FailQuery>,
+) -> Response {
+    let action = match query.command.as_str() {
+        "build" => Action::Build,
+        "rebuild" => Action::Rebuild,
+        "boot" => Action::Boot,
+        "shutdown" => Action::Shutdown,
+        "reboot" => Action::Reboot,
+        other => {
+            return error(
+                StatusCode::BAD_REQUEST,
+                format!("Unknown command {other}"),
+            );
+        }
+    };
+    sim.jobs().fail_next(action);
+    data(json!({ "fail_next": action.command() }))
+}
@@ -0,0 +1,176 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+use ipnet::Ipv4Net;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::net::Ipv4Addr;
+use std::path::Path;
+
+use super::jobs::BuildSpec;
+use crate::endpoints::{
+    Details, IPv4, IPv6, Image, Invoices, Location, MonthlyBw, Package,
+    SSHKeys, Server, Sizes, SrvJob, Zone,
+};
+use crate::errors::NaApiError;
+use crate::ids::{LocationId, MbPkgId};
+use crate::net::MacAddr;
+use crate::status::ServerState;
+
+/// The seed shipped with the crate, two servers and a bit of everything
+pub const DEFAULT_SEED: &str = include_str!("../../fixtures/sim/seed.json");
+
+/// Where ordered packages get their address, TEST-NET-3
+const ORDER_NET: Ipv4Net =
+    Ipv4Net::new_assert(Ipv4Addr::new(203, 0, 113, 0), 24);
+
+/// Everything the simulator knows about, in the same shape the API sends
+///
+/// Per server collections are keyed by mbpkgid, sizes by location id
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SimState {
+    pub details: Option<Details>,
+    pub invoices: Vec<Invoices>,
+    pub ssh_keys: Vec<SSHKeys>,
+    pub servers: Vec<Server>,
+    pub packages: Vec<Package>,
+    pub jobs: BTreeMap<MbPkgId, Vec<SrvJob>>,
+    pub ipv4: BTreeMap<MbPkgId, Vec<IPv4>>,
+    pub ipv6: BTreeMap<MbPkgId, Vec<IPv6>>,
+    pub bandwidth: BTreeMap<MbPkgId, Vec<MonthlyBw>>,
+    pub images: Vec<Image>,
+    pub locations: Vec<Location>,
+    pub sizes: BTreeMap<LocationId, Vec<Sizes>>,
+    pub zones: Vec<Zone>,
+}
+
+impl SimState {
+    /// State from the seed shipped with the crate
+    pub fn default_seed() -> Self {
+        serde_json::from_str(DEFAULT_SEED).expect("shipped seed is valid")
+    }
+
+    /// Load a seed, either one JSON file holding every collection or a
+    /// directory with one `<collection>.json` file each (`servers.json`,
+    /// `jobs.json`...), collections without a file start out empty
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, NaApiError> {
+        let path = path.as_ref();
+        let seed = if path.is_dir() {
+            let mut seed = Map::new();
+            let entries =
+                std::fs::read_dir(path).map_err(|e| load_error(path, e))?;
+            for entry in entries {
+                let file = entry.map_err(|e| load_error(path, e))?.path();
+                if file.extension().and_then(|e| e.to_str()) != Some("json") {
+                    continue;
+                }
+                let Some(name) = file.file_stem().and_then(|n| n.to_str())
+                else {
+                    continue;
+                };
+                seed.insert(name.to_owned(), read_json(&file)?);
+            }
+            Value::Object(seed)
+        } else {
+            read_json(path)?
+        };
+        serde_json::from_value(seed).map_err(|e| {
+            NaApiError::UnknownError(format!(
+                "Invalid seed {}: {e}",
+                path.display()
+            ))
+        })
+    }
+
+    /// A new package, not built yet, for `build` to go on. The first
+    /// server and package are the template, the new one gets the next
+    /// mbpkgid, a free address out of `ORDER_NET` and a MAC of its own.
+    /// The error is the message for the 400
+    pub(crate) fn order(
+        &mut self, build: &BuildSpec,
+    ) -> Result<MbPkgId, &'static str> {
+        const NOT_OFFERED: &str = "The plan is not offered at that location";
+        let location_id = build.location_id.ok_or(NOT_OFFERED)?;
+        let plan_id = build.plan_id.ok_or(NOT_OFFERED)?;
+        let location = self
+            .locations
+            .iter()
+            .find(|l| l.id == location_id)
+            .ok_or(NOT_OFFERED)?;
+        let size = self
+            .sizes
+            .get(&location_id)
+            .and_then(|sizes| sizes.iter().find(|s| s.plan_id == plan_id))
+            .ok_or(NOT_OFFERED)?;
+        // "RDU - Raleigh, NC" is in Raleigh
+        let city = location
+            .name
+            .split(" - ")
+            .nth(1)
+            .and_then(|rest| rest.split(',').next())
+            .unwrap_or(&location.name)
+            .to_owned();
+        let plan = size.plan.clone();
+        let ip = self.free_address().ok_or("No addresses left to order")?;
+        const NO_TEMPLATE: &str = "There is no server to copy";
+        let mut server = self.servers.first().ok_or(NO_TEMPLATE)?.clone();
+        let mut package = self.packages.first().ok_or(NO_TEMPLATE)?.clone();
+
+        let next = self.servers.iter().map(|s| s.mbpkgid.get()).max();
+        let next = next.unwrap_or_default() + 1;
+        let mbpkgid = MbPkgId(next);
+        let [_, _, hi, lo] = ip.octets();
+        let fqdn = build.fqdn.clone().unwrap_or_default();
+
+        server.city = city.clone();
+        server.fqdn = fqdn.clone();
+        server.domu_package = next;
+        server.mbpkgid = mbpkgid;
+        server.location_id = location_id;
+        server.plan_id = plan_id;
+        server.ip = Some(ip);
+        server.ipv6 = None;
+        server.state = ServerState::Stopped;
+        server.uptime = String::new();
+        server.installed = 0;
+        server.nic1_mac = Some(MacAddr([0x52, 0x54, 0x00, 0xff, hi, lo]));
+        server.nic2_mac = None;
+
+        package.mbpkgid = mbpkgid;
+        package.fqdn = fqdn;
+        package.name = plan.clone();
+        package.domu_package = next;
+        package.locked = 0;
+        package.package = plan;
+        package.ipv6 = String::new();
+        package.city = city;
+        package.ip = ip.to_string();
+        package.installed = 0;
+        package.state = ServerState::Stopped;
+        package.uptime = String::new();
+        package.is_building = 0;
+
+        self.servers.push(server);
+        self.packages.push(package);
+        Ok(mbpkgid)
+    }
+
+    /// The lowest address in `ORDER_NET` no server has yet
+    fn free_address(&self) -> Option<Ipv4Addr> {
+        ORDER_NET
+            .hosts()
+            .find(|ip| self.servers.iter().all(|s| s.ip != Some(*ip)))
+    }
+}
+
+fn read_json(path: &Path) -> Result<Value, NaApiError> {
+    let text =
+        std::fs::read_to_string(path).map_err(|e| load_error(path, e))?;
+    serde_json::from_str(&text).map_err(|e| load_error(path, e))
+}
+
+fn load_error(path: &Path, e: impl std::fmt::Display) -> NaApiError {
+    NaApiError::UnknownError(format!("Could not load {}: {e}", path.display()))
+}
@@ -0,0 +1,249 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! States and statuses the API sends as strings or numbers
+//!
+//! Every enum has an `Unknown` variant keeping whatever was sent, so a
+//! value this version has not heard of yet still deserializes. Strings
+//! are matched without regard to case
+//!
+//! ```rust
+//! use rnaapi::{JobStatus, ServerState};
+//!
+//! assert!(ServerState::from("running").is_running());
+//! assert_eq!(ServerState::from("MIGRATING").to_string(), "MIGRATING");
+//! assert!(JobStatus::from(3).is_failed());
+//! ```
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+macro_rules! string_enum {
+    (
+        $(#[$meta:meta])*
+        $name:ident {
+            $($(#[$vmeta:meta])* $variant:ident = $text:literal,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$vmeta])* $variant,)*
+            /// Not known to this version, kept as sent
+            Unknown(String),
+        }
+
+        impl $name {
+            /// The value as the API spells it
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $text,)*
+                    $name::Unknown(text) => text,
+                }
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(text: &str) -> Self {
+                $(if text.eq_ignore_ascii_case($text) {
+                    return $name::$variant;
+                })*
+                $name::Unknown(text.to_owned())
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Infallible;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Ok(s.into())
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(
+                &self, serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<Self, D::Error> {
+                let text = String::deserialize(deserializer)?;
+                Ok(text.as_str().into())
+            }
+        }
+    };
+}
+
+string_enum!(
+    /// `Server.state`, `Package.state` and `SrvStatus.status`
+    ServerState {
+        Running = "RUNNING",
+        Stopped = "STOPPED",
+        Building = "BUILDING",
+        Booting = "BOOTING",
+        ShuttingDown = "SHUTTING_DOWN",
+        Rebooting = "REBOOTING",
+    }
+);
+
+impl ServerState {
+    pub fn is_running(&self) -> bool {
+        *self == ServerState::Running
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        *self == ServerState::Stopped
+    }
+
+    /// In the middle of a job, IE building or rebooting
+    pub fn is_busy(&self) -> bool {
+        matches!(
+            self,
+            ServerState::Building
+                | ServerState::Booting
+                | ServerState::ShuttingDown
+                | ServerState::Rebooting
+        )
+    }
+}
+
+string_enum!(
+    /// `Server.status`, the billing side of the server
+    ServerStatus {
+        Active = "ACTIVE",
+        Pending = "PENDING",
+        Suspended = "SUSPENDED",
+        Terminated = "TERMINATED",
+        Cancelled = "CANCELLED",
+    }
+);
+
+impl ServerStatus {
+    pub fn is_active(&self) -> bool {
+        *self == ServerStatus::Active
+    }
+}
+
+string_enum!(
+    /// `Package.package_status`
+    PackageStatus {
+        Active = "Active",
+        Pending = "Pending",
+        Suspended = "Suspended",
+        Terminated = "Terminated",
+        Cancelled = "Cancelled",
+    }
+);
+
+impl PackageStatus {
+    pub fn is_active(&self) -> bool {
+        *self == PackageStatus::Active
+    }
+}
+
+string_enum!(
+    /// `Zone.zone_type`, also the `type` filter of `Zone::get_all`
+    ZoneType {
+        Native = "NATIVE",
+        Master = "MASTER",
+        Slave = "SLAVE",
+    }
+);
+
+/// `SrvJob.status`, sent as a number
+///
+/// 2 for a finished job is what the recorded
+/// `tests/fixtures/get_cloud_server_7001_jobs.json` shows, 0, 1 and 3 are
+/// the order `sim` walks a job through and have not been seen from the
+/// live API yet. Any other number decodes to `Unknown` rather than failing
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum JobStatus {
+    /// waiting for a worker
+    Queued,
+    /// being worked on
+    Running,
+    /// went through
+    Done,
+    /// gave up
+    Failed,
+    /// Not known to this version, kept as sent
+    Unknown(u32),
+}
+
+impl JobStatus {
+    /// The number the API sends
+    pub fn code(self) -> u32 {
+        match self {
+            JobStatus::Queued => 0,
+            JobStatus::Running => 1,
+            JobStatus::Done => 2,
+            JobStatus::Failed => 3,
+            JobStatus::Unknown(code) => code,
+        }
+    }
+
+    pub fn is_running(self) -> bool {
+        self == JobStatus::Running
+    }
+
+    pub fn is_failed(self) -> bool {
+        self == JobStatus::Failed
+    }
+
+    /// Done or failed, the job won't change any more
+    pub fn is_terminal(self) -> bool {
+        matches!(self, JobStatus::Done | JobStatus::Failed)
+    }
+}
+
+impl From<u32> for JobStatus {
+    fn from(code: u32) -> Self {
+        match code {
+            0 => JobStatus::Queued,
+            1 => JobStatus::Running,
+            2 => JobStatus::Done,
+            3 => JobStatus::Failed,
+            code => JobStatus::Unknown(code),
+        }
+    }
+}
+
+impl fmt::Display for JobStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JobStatus::Queued => f.write_str("queued"),
+            JobStatus::Running => f.write_str("running"),
+            JobStatus::Done => f.write_str("done"),
+            JobStatus::Failed => f.write_str("failed"),
+            JobStatus::Unknown(code) => write!(f, "unknown ({code})"),
+        }
+    }
+}
+
+impl Serialize for JobStatus {
+    fn serialize<S: Serializer>(
+        &self, serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for JobStatus {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        u32::deserialize(deserializer).map(JobStatus::from)
+    }
+}
@@ -0,0 +1,243 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! Lists handed out item by item as they are parsed
+//!
+//! `EndpointGetAll::stream_all` fetches a page, parses its `data` one
+//! item at a time on a blocking thread and passes each item on as soon
+//! as it is decoded. Only a few parsed items and the body of one page
+//! are held at once, the next page is fetched when the current one is
+//! used up. Needs to run inside a tokio runtime
+//!
+//! ```rust,no_run
+//! # async fn report(client: &rnaapi::NaClient) -> anyhow::Result<()> {
+//! use futures::TryStreamExt;
+//! use rnaapi::EndpointGetAll;
+//! use rnaapi::endpoints::Invoices;
+//!
+//! let mut invoices = Invoices::stream_all(client, ());
+//! while let Some(invoice) = invoices.try_next().await? {
+//!     println!("{} {}", invoice.id, invoice.subtotal);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use futures::stream::{self, BoxStream};
+use reqwest::Method;
+use serde::Serialize;
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess,
+    Visitor,
+};
+use serde_json::Value;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use tokio::sync::mpsc::{self, Sender};
+
+use crate::envelope::{Envelope, Pager};
+use crate::errors::NaApiError;
+use crate::{NaClient, RequestBody, json_pointer, no_data};
+
+/// Items of a list, in order, ending early on the first error
+pub type ItemStream<T> = BoxStream<'static, Result<T, NaApiError>>;
+
+/// Parsed items waiting for the consumer, the parser blocks past this
+const BUFFER: usize = 64;
+
+/// Stream the items at `path`, following pages
+pub(crate) fn items<T>(
+    client: NaClient, endpoint: &'static str, path: Result<String, NaApiError>,
+) -> ItemStream<T>
+where
+    T: DeserializeOwned + Serialize + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel(BUFFER);
+    tokio::spawn(async move {
+        if let Err(e) = produce(&client, endpoint, path, &tx).await {
+            // nothing to do when the consumer is already gone
+            let _ = tx.send(Err(e)).await;
+        }
+    });
+    Box::pin(stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|item| (item, rx))
+    }))
+}
+
+/// One stream with just the error, for endpoints that can't stream
+pub(crate) fn error<T: Send + 'static>(error: NaApiError) -> ItemStream<T> {
+    Box::pin(stream::once(async { Err(error) }))
+}
+
+async fn produce<T>(
+    client: &NaClient, endpoint: &'static str,
+    path: Result<String, NaApiError>, tx: &Sender<Result<T, NaApiError>>,
+) -> Result<(), NaApiError>
+where
+    T: DeserializeOwned + Serialize + Send + 'static,
+{
+    let mut pager = Pager::new(&path?);
+    loop {
+        let path = pager.path().to_owned();
+        let body = client
+            .request(Method::GET, &path, RequestBody::Empty)
+            .await?;
+        // parsed again for the comparison, only when asked for
+        let sent = client.drift.is_on().then(|| body.clone());
+        let sender = tx.clone();
+        let page_path = path.clone();
+        let page = tokio::task::spawn_blocking(move || {
+            parse_page(endpoint, &page_path, &body, &sender)
+        })
+        .await
+        .map_err(|e| {
+            NaApiError::UnknownError(format!("Parsing {path} failed: {e}"))
+        })?;
+        let Some(page) = page? else {
+            // the consumer dropped the stream
+            return Ok(());
+        };
+        let (next_page, pages) = (page.page, page.pages);
+        client
+            .unwrap_data(&path, page)?
+            .ok_or_else(|| no_data(&path))?;
+        if let Some(body) = sent
+            && let Ok(Envelope {
+                data: Some(kept), ..
+            }) = serde_json::from_slice::<Envelope<Vec<T>>>(&body)
+        {
+            client.check_drift(endpoint, &path, &body, &kept);
+        }
+        if !pager.next(next_page, pages)? {
+            return Ok(());
+        }
+    }
+}
+
+/// Parse one body, sending the items on as they come. Returns the
+/// envelope without its items, or `None` when the receiver went away
+fn parse_page<T: DeserializeOwned>(
+    endpoint: &str, path: &str, body: &[u8], tx: &Sender<Result<T, NaApiError>>,
+) -> Result<Option<Envelope<()>>, NaApiError> {
+    let mut de = serde_json::Deserializer::from_slice(body);
+    let mut track = serde_path_to_error::Track::new();
+    let seed = EnvelopeSeed {
+        tx,
+        marker: PhantomData,
+    };
+    let parsed = seed.deserialize(serde_path_to_error::Deserializer::new(
+        &mut de, &mut track,
+    ));
+    let pointer = || json_pointer(&track.path());
+    let envelope = match parsed {
+        Ok(envelope) => envelope,
+        Err(_) if tx.is_closed() => return Ok(None),
+        Err(e) => {
+            return Err(NaApiError::Deserialize {
+                endpoint: endpoint.to_owned(),
+                path: path.to_owned(),
+                pointer: pointer(),
+                source: Arc::new(e),
+            });
+        }
+    };
+    de.end().map_err(|e| NaApiError::Deserialize {
+        endpoint: endpoint.to_owned(),
+        path: path.to_owned(),
+        pointer: String::new(),
+        source: Arc::new(e),
+    })?;
+    Ok(Some(envelope))
+}
+
+/// Reads the envelope, handing the `data` items to `tx` one by one
+struct EnvelopeSeed<'a, T> {
+    tx: &'a Sender<Result<T, NaApiError>>,
+    marker: PhantomData<T>,
+}
+
+impl<'de, T: DeserializeOwned> DeserializeSeed<'de> for EnvelopeSeed<'_, T> {
+    type Value = Envelope<()>;
+
+    fn deserialize<D: de::Deserializer<'de>>(
+        self, deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, T: DeserializeOwned> Visitor<'de> for EnvelopeSeed<'_, T> {
+    type Value = Envelope<()>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a response envelope")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(
+        self, mut map: A,
+    ) -> Result<Self::Value, A::Error> {
+        let mut envelope = Envelope {
+            code: None,
+            message: None,
+            page: None,
+            pages: None,
+            data: None,
+        };
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "code" => envelope.code = map.next_value::<Option<Value>>()?,
+                "message" => {
+                    envelope.message = map.next_value::<Option<Value>>()?
+                }
+                "page" => envelope.page = map.next_value()?,
+                "pages" => envelope.pages = map.next_value()?,
+                "data" => {
+                    map.next_value_seed(ItemsSeed {
+                        tx: self.tx,
+                        marker: PhantomData,
+                    })?;
+                    envelope.data = Some(());
+                }
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(envelope)
+    }
+}
+
+/// Reads the `data` list, sending each item as soon as it is decoded
+struct ItemsSeed<'a, T> {
+    tx: &'a Sender<Result<T, NaApiError>>,
+    marker: PhantomData<T>,
+}
+
+impl<'de, T: DeserializeOwned> DeserializeSeed<'de> for ItemsSeed<'_, T> {
+    type Value = ();
+
+    fn deserialize<D: de::Deserializer<'de>>(
+        self, deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, T: DeserializeOwned> Visitor<'de> for ItemsSeed<'_, T> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a list")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(
+        self, mut seq: A,
+    ) -> Result<Self::Value, A::Error> {
+        while let Some(item) = seq.next_element::<T>()? {
+            self.tx.blocking_send(Ok(item)).map_err(|_| {
+                de::Error::custom("stream dropped, stopped parsing")
+            })?;
+        }
+        Ok(())
+    }
+}
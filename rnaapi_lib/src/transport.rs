@@ -0,0 +1,297 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+use async_trait::async_trait;
+use reqwest::header::HeaderMap;
+use reqwest::{Method, StatusCode};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+use crate::RequestBody;
+use crate::errors::NaApiError;
+use crate::secret::REDACTED;
+
+/// A fully built request, ready to go on the wire
+#[derive(Clone)]
+pub struct HttpRequest {
+    pub method: Method,
+    /// endpoint path as handed to `NaClient`, IE `cloud/server?mbpkgid=1`
+    pub path: String,
+    /// complete url, including the key when it goes in the query string
+    pub url: String,
+    pub headers: HeaderMap,
+    pub body: RequestBody,
+}
+
+impl fmt::Debug for HttpRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the url may carry the key, header values are marked sensitive
+        let url = match reqwest::Url::parse(&self.url) {
+            Ok(url) => redact_query(url).to_string(),
+            Err(_) => REDACTED.to_owned(),
+        };
+        f.debug_struct("HttpRequest")
+            .field("method", &self.method)
+            .field("path", &self.path)
+            .field("url", &url)
+            .field("headers", &self.headers)
+            .field("body", &self.body)
+            .finish()
+    }
+}
+
+/// Whatever came back, before any parsing
+#[derive(Clone, Debug)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub fn new(status: StatusCode, body: impl Into<Vec<u8>>) -> Self {
+        HttpResponse {
+            status,
+            headers: HeaderMap::new(),
+            body: body.into(),
+        }
+    }
+
+    /// A response with a JSON body
+    pub fn json(status: StatusCode, body: &Value) -> Self {
+        HttpResponse::new(status, body.to_string())
+    }
+}
+
+/// Executes requests for a `NaClient`
+///
+/// `ReqwestTransport` talks to the network, `MockTransport` answers from
+/// memory so code built on the library can be tested offline
+#[async_trait]
+pub trait Transport: fmt::Debug + Send + Sync {
+    /// Send the request, only failures to get any response at all are
+    /// errors, non success statuses are handled by the caller
+    async fn execute(
+        &self, request: HttpRequest,
+    ) -> Result<HttpResponse, NaApiError>;
+}
+
+/// The real thing, backed by a `reqwest::Client`
+#[derive(Clone, Debug, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(client: reqwest::Client) -> Self {
+        ReqwestTransport { client }
+    }
+
+    pub fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    fn error(path: &str, error: reqwest::Error) -> NaApiError {
+        NaApiError::Transport {
+            path: path.to_owned(),
+            source: Arc::new(redact_url(error)),
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for ReqwestTransport {
+    async fn execute(
+        &self, request: HttpRequest,
+    ) -> Result<HttpResponse, NaApiError> {
+        let builder = self
+            .client
+            .request(request.method, request.url)
+            .headers(request.headers);
+        let builder = match request.body {
+            RequestBody::Empty => builder,
+            RequestBody::Form(pairs) => builder.form(&pairs),
+            RequestBody::Json(value) => builder.json(&value),
+        };
+        let response = builder
+            .send()
+            .await
+            .map_err(|e| Self::error(&request.path, e))?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| Self::error(&request.path, e))?;
+        Ok(HttpResponse {
+            status,
+            headers,
+            body: body.to_vec(),
+        })
+    }
+}
+
+/// Scrub the key from the url reqwest keeps inside its errors
+fn redact_url(error: reqwest::Error) -> reqwest::Error {
+    let Some(url) = error.url() else {
+        return error;
+    };
+    let url = redact_query(url.clone());
+    error.with_url(url)
+}
+
+/// Replace the value of a `key` query parameter
+fn redact_query(mut url: reqwest::Url) -> reqwest::Url {
+    if !url.query_pairs().any(|(name, _)| name == "key") {
+        return url;
+    }
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(name, value)| {
+            let value = if name == "key" {
+                REDACTED.to_owned()
+            } else {
+                value.into_owned()
+            };
+            (name.into_owned(), value)
+        })
+        .collect();
+    url.query_pairs_mut().clear().extend_pairs(pairs);
+    url
+}
+
+/// Error handed out by `MockTransport::fail`
+#[derive(Clone, Debug, Error)]
+#[error("{0}")]
+pub struct MockFailure(pub String);
+
+#[derive(Clone, Debug)]
+enum Canned {
+    Response(HttpResponse),
+    Fail(String),
+}
+
+/// In memory transport with canned responses per method and path
+///
+/// Responses for the same route are handed out in order, the last one
+/// repeats. Anything without a canned response gets an API style 404.
+/// Every request is kept so tests can look at what was sent
+///
+/// ```rust
+/// use rnaapi::transport::MockTransport;
+/// use serde_json::json;
+///
+/// let mock = MockTransport::new()
+///     .get("cloud/servers", json!({"data": []}))
+///     .status("cloud/status/1", 503, json!({"message": "busy"}));
+/// ```
+#[derive(Debug, Default)]
+pub struct MockTransport {
+    routes: Mutex<HashMap<(Method, String), VecDeque<Canned>>>,
+    requests: Mutex<Vec<HttpRequest>>,
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answer a GET of `path` with 200 and `body`
+    pub fn get(self, path: &str, body: Value) -> Self {
+        self.respond(
+            Method::GET,
+            path,
+            HttpResponse::json(StatusCode::OK, &body),
+        )
+    }
+
+    /// Answer a GET of `path` with any status and `body`
+    pub fn status(self, path: &str, status: u16, body: Value) -> Self {
+        let status =
+            StatusCode::from_u16(status).unwrap_or(StatusCode::BAD_REQUEST);
+        self.respond(Method::GET, path, HttpResponse::json(status, &body))
+    }
+
+    /// Answer `method` on `path` with 200 and `body`
+    pub fn on(self, method: Method, path: &str, body: Value) -> Self {
+        self.respond(method, path, HttpResponse::json(StatusCode::OK, &body))
+    }
+
+    /// Fail `method` on `path` as if the network went away
+    pub fn fail(self, method: Method, path: &str, message: &str) -> Self {
+        self.push(method, path, Canned::Fail(message.to_owned()));
+        self
+    }
+
+    /// Answer `method` on `path` with a complete response
+    pub fn respond(
+        self, method: Method, path: &str, response: HttpResponse,
+    ) -> Self {
+        self.push(method, path, Canned::Response(response));
+        self
+    }
+
+    /// Add a canned response to an already shared mock
+    pub fn add(&self, method: Method, path: &str, response: HttpResponse) {
+        self.push(method, path, Canned::Response(response));
+    }
+
+    /// Every request executed so far, oldest first
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    fn push(&self, method: Method, path: &str, canned: Canned) {
+        self.routes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry((method, path.to_owned()))
+            .or_default()
+            .push_back(canned);
+    }
+}
+
+#[async_trait]
+impl Transport for MockTransport {
+    async fn execute(
+        &self, request: HttpRequest,
+    ) -> Result<HttpResponse, NaApiError> {
+        self.requests
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(request.clone());
+        let canned = {
+            let mut routes =
+                self.routes.lock().unwrap_or_else(|e| e.into_inner());
+            let key = (request.method.clone(), request.path.clone());
+            match routes.get_mut(&key) {
+                Some(queue) if queue.len() > 1 => queue.pop_front(),
+                Some(queue) => queue.front().cloned(),
+                None => None,
+            }
+        };
+        match canned {
+            Some(Canned::Response(response)) => Ok(response),
+            Some(Canned::Fail(message)) => Err(NaApiError::Transport {
+                path: request.path,
+                source: Arc::new(MockFailure(message)),
+            }),
+            None => Ok(HttpResponse::json(
+                StatusCode::NOT_FOUND,
+                &serde_json::json!({
+                    "code": 404,
+                    "message": format!(
+                        "no canned response for {} {}",
+                        request.method, request.path
+                    ),
+                }),
+            )),
+        }
+    }
+}
@@ -0,0 +1,210 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! Builds are checked against the catalog, then hand back their job
+use rnaapi::endpoints::{
+    BuildRequest, Package, Server, ServerGetOneParams, SrvJob,
+    SrvJobGetOneParams,
+};
+use rnaapi::errors::NaApiError;
+use rnaapi::sim::{RunningSim, SimState};
+use rnaapi::{
+    EndpointGetAll, EndpointGetOne, JobStatus, LocationId, MbPkgId, NaClient,
+    OsId, PlanId, RequestBody, SshKeyId,
+};
+use std::net::Ipv4Addr;
+use std::time::Duration;
+
+mod common;
+use common::sim_with;
+
+fn web2() -> BuildRequest {
+    BuildRequest::new(LocationId(3), PlanId(102), OsId(40), "web2.example.net")
+        .ssh_key(SshKeyId(301))
+        .user_data("#cloud-config\n")
+}
+
+async fn server(client: &NaClient, mbpkgid: MbPkgId) -> Server {
+    Server::get_one(client, ServerGetOneParams { mbpkgid })
+        .await
+        .expect("server")
+}
+
+async fn finish(running: &RunningSim, client: &NaClient, job: &SrvJob) {
+    running.advance(Duration::from_secs(600));
+    let params = SrvJobGetOneParams {
+        mbpkgid: job.mbpkgid.expect("mbpkgid"),
+        job_id: job.id,
+    };
+    let job = SrvJob::get_one(client, params).await.expect("job");
+    assert_eq!(job.status, JobStatus::Done);
+}
+
+/// The field a request is turned down for
+async fn rejected(client: &NaClient, request: BuildRequest) -> &'static str {
+    match Server::build(client, &request).await {
+        Err(NaApiError::Validation { field, .. }) => field,
+        other => panic!("expected a validation error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn on_a_package() {
+    let (running, client) = sim_with(SimState::default_seed()).await;
+    let request = web2().on_package(MbPkgId(7002));
+    let job = Server::build(&client, &request).await.expect("build");
+    assert_eq!(job.command, "build");
+    assert_eq!(job.mbpkgid, Some(MbPkgId(7002)));
+
+    finish(&running, &client, &job).await;
+    let server = server(&client, MbPkgId(7002)).await;
+    assert_eq!(server.fqdn, "web2.example.net");
+    assert_eq!(server.location_id, LocationId(3));
+    assert_eq!(server.plan_id, PlanId(102));
+    assert_eq!(server.os_id, OsId(40));
+    assert_eq!(server.installed, 1);
+}
+
+#[tokio::test]
+async fn on_a_new_package() {
+    let (running, client) = sim_with(SimState::default_seed()).await;
+    let job = Server::build(&client, &web2()).await.expect("build");
+    let mbpkgid = job.mbpkgid.expect("the new package");
+    assert!(mbpkgid > MbPkgId(7002));
+    assert!(
+        Package::get_all(&client, ())
+            .await
+            .expect("packages")
+            .iter()
+            .any(|p| p.mbpkgid == mbpkgid)
+    );
+
+    finish(&running, &client, &job).await;
+    let server = server(&client, mbpkgid).await;
+    assert_eq!(server.fqdn, "web2.example.net");
+    assert_eq!(server.city, "Raleigh");
+    assert_eq!(server.ip, Some(Ipv4Addr::new(203, 0, 113, 1)));
+    assert!(server.state.is_running());
+}
+
+#[tokio::test]
+async fn checked_against_the_catalog() {
+    let (_running, client) = sim_with(SimState::default_seed()).await;
+    let bad_name = BuildRequest::new(
+        LocationId(3),
+        PlanId(102),
+        OsId(40),
+        "-web2.example.net",
+    );
+    assert_eq!(rejected(&client, bad_name).await, "hostname");
+
+    let mut request = web2();
+    request.location_id = LocationId(9);
+    assert_eq!(rejected(&client, request).await, "location_id");
+    let mut request = web2();
+    request.location_id = LocationId(99);
+    assert_eq!(rejected(&client, request).await, "location_id");
+
+    let mut request = web2();
+    request.plan_id = PlanId(104);
+    assert_eq!(rejected(&client, request).await, "plan_id");
+    // sold there, but sold out
+    let mut request = web2();
+    request.location_id = LocationId(7);
+    request.plan_id = PlanId(104);
+    assert_eq!(rejected(&client, request).await, "plan_id");
+
+    let mut request = web2();
+    request.os_id = OsId(99);
+    assert_eq!(rejected(&client, request).await, "os_id");
+    let request = web2().ssh_key(SshKeyId(999));
+    assert_eq!(rejected(&client, request).await, "ssh_key_ids");
+    let request = web2().on_package(MbPkgId(1));
+    assert_eq!(rejected(&client, request).await, "mbpkgid");
+
+    // nothing was submitted
+    assert_eq!(
+        Package::get_all(&client, ()).await.expect("packages").len(),
+        2
+    );
+}
+
+#[test]
+fn paths_and_form() {
+    let request = web2();
+    assert_eq!(request.path(), "cloud/server/build");
+    assert_eq!(
+        request.clone().on_package(MbPkgId(7002)).path(),
+        "cloud/server/build/7002"
+    );
+    let RequestBody::Form(pairs) = RequestBody::form(&request).expect("form")
+    else {
+        panic!("not a form");
+    };
+    let pair = |name: &str, value: &str| (name.to_owned(), value.to_owned());
+    assert!(pairs.contains(&pair("hostname", "web2.example.net")));
+    assert!(pairs.contains(&pair("ssh_key_ids[]", "301")));
+    assert!(!pairs.iter().any(|(name, _)| name == "mbpkgid"));
+}
+
+#[tokio::test]
+async fn new_packages_run_out_of_addresses() {
+    let mut state = SimState::default_seed();
+    let template = state.servers[0].clone();
+    // everything in 203.0.113.0/24 but .77 is taken
+    for lo in (1..=254).filter(|lo| *lo != 77) {
+        let mut server = template.clone();
+        server.mbpkgid = MbPkgId(8000 + u32::from(lo));
+        server.ip = Some(Ipv4Addr::new(203, 0, 113, lo));
+        state.servers.push(server);
+    }
+    let (running, client) = sim_with(state).await;
+    let job = Server::build(&client, &web2()).await.expect("build");
+    let mbpkgid = job.mbpkgid.expect("the new package");
+    finish(&running, &client, &job).await;
+    let server = server(&client, mbpkgid).await;
+    assert_eq!(server.ip, Some(Ipv4Addr::new(203, 0, 113, 77)));
+
+    let err = Server::build(&client, &web2()).await.unwrap_err();
+    assert!(err.to_string().contains("No addresses left"), "{err}");
+}
+
+#[tokio::test]
+async fn hostnames() {
+    let (_running, client) = sim_with(SimState::default_seed()).await;
+    let named = |hostname: String| {
+        BuildRequest::new(LocationId(3), PlanId(102), OsId(40), hostname)
+    };
+    let label = "a".repeat(63);
+    // 63 + 1 + 63 + 1 + 63 + 1 + 61
+    let longest = format!("{label}.{label}.{label}.{}", "b".repeat(61));
+    assert_eq!(longest.len(), 253);
+    for hostname in ["web2", "web-2.example.net", &label, &longest] {
+        named(hostname.to_owned())
+            .validate(&client)
+            .await
+            .unwrap_or_else(|e| panic!("{hostname} should do: {e}"));
+    }
+
+    let too_long = format!("{longest}c");
+    let long_label = format!("{label}a.example.net");
+    for hostname in [
+        "web_2.example.net",
+        "web 2.example.net",
+        "wéb2.example.net",
+        "web2.example.net/",
+        "web2-.example.net",
+        "web2..example.net",
+        "web2.example.net.",
+        "",
+        &long_label,
+        &too_long,
+    ] {
+        match named(hostname.to_owned()).validate(&client).await {
+            Err(NaApiError::Validation { field, .. }) => {
+                assert_eq!(field, "hostname", "{hostname}")
+            }
+            other => panic!("{hostname:?} should not do, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,130 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! The builder's http settings reach reqwest, against a local listener
+use axum::Router;
+use axum::http::HeaderMap;
+use axum::http::header::USER_AGENT;
+use axum::routing::get;
+use rnaapi::builder::USER_AGENT as DEFAULT_USER_AGENT;
+use rnaapi::{NaClient, Resolver, RetryPolicy};
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+/// `/api/agent` echoes the User-Agent, `/api/slow` takes its time
+async fn listener() -> SocketAddr {
+    let app = Router::new()
+        .route(
+            "/api/agent",
+            get(|headers: HeaderMap| async move {
+                let agent = headers
+                    .get(USER_AGENT)
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or_default()
+                    .to_owned();
+                axum::Json(json!({"data": agent}))
+            }),
+        )
+        .route(
+            "/api/slow",
+            get(|| async {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                axum::Json(json!({"data": null}))
+            }),
+        );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind");
+    let addr = listener.local_addr().expect("addr");
+    tokio::spawn(async move { axum::serve(listener, app).await });
+    addr
+}
+
+fn builder(address: String) -> rnaapi::NaClientBuilder {
+    NaClient::builder("test-key", address)
+        .retry_policy(RetryPolicy::none())
+        .resolver(Resolver::System)
+}
+
+#[tokio::test]
+async fn user_agent() {
+    let addr = listener().await;
+    let address = format!("http://{addr}/api/");
+    let client = builder(address.clone()).build().expect("client");
+    let agent: Value = client.get_data("agent").await.expect("agent");
+    assert_eq!(agent, DEFAULT_USER_AGENT);
+    assert!(DEFAULT_USER_AGENT.starts_with("rnaapi/"));
+
+    let client = builder(address)
+        .user_agent("nightly-inventory/1.0")
+        .build()
+        .expect("client");
+    let agent: Value = client.get_data("agent").await.expect("agent");
+    assert_eq!(agent, "nightly-inventory/1.0");
+}
+
+#[tokio::test]
+async fn fixed_resolver() {
+    let addr = listener().await;
+    // the port comes from the url, only the address from the resolver
+    let host = "api.rnaapi.invalid";
+    let address = format!("http://{host}:{}/api/", addr.port());
+    let hosts = BTreeMap::from([(host.to_owned(), vec![addr])]);
+    let client = builder(address.clone())
+        .resolver(Resolver::Fixed(hosts))
+        .build()
+        .expect("client");
+    client
+        .get_data("agent")
+        .await
+        .expect("resolved to the listener");
+
+    // `.invalid` never resolves for real
+    let client = builder(address).build().expect("client");
+    let err = client.get_data("agent").await.unwrap_err();
+    assert!(err.is_transport(), "{err}");
+}
+
+#[tokio::test]
+async fn timeout() {
+    let addr = listener().await;
+    let client = builder(format!("http://{addr}/api/"))
+        .connect_timeout(Duration::from_secs(1))
+        .timeout(Duration::from_millis(200))
+        .build()
+        .expect("client");
+    let start = Instant::now();
+    let err = client.get_data("slow").await.unwrap_err();
+    assert!(err.is_transport(), "{err}");
+    assert!(start.elapsed() < Duration::from_secs(5));
+    // the rest of the client is unaffected
+    client.get_data("agent").await.expect("agent");
+}
+
+#[test]
+fn bad_ca_bundles_fail_the_build() {
+    let garbage = "-----BEGIN CERTIFICATE-----\nnot base64 at all!\n\
+                   -----END CERTIFICATE-----\n";
+    let err = builder("https://vapi2.netactuate.com/api/".to_owned())
+        .ca_bundle_pem(garbage)
+        .build()
+        .unwrap_err();
+    assert!(err.to_string().contains("CA bundle"), "{err}");
+
+    let err = builder("https://vapi2.netactuate.com/api/".to_owned())
+        .ca_bundle_file("/nonexistent/ca.pem")
+        .build()
+        .unwrap_err();
+    assert!(err.to_string().contains("/nonexistent/ca.pem"), "{err}");
+}
+
+#[test]
+fn bad_proxy_fails_the_build() {
+    let err = builder("https://vapi2.netactuate.com/api/".to_owned())
+        .proxy("not a url")
+        .build()
+        .unwrap_err();
+    assert!(err.to_string().contains("proxy"), "{err}");
+}
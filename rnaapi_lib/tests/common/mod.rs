@@ -0,0 +1,76 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! Helpers shared by the integration tests, each test file that needs
+//! them has a `mod common;` and uses only some of them
+#![allow(dead_code)]
+use rnaapi::drift::SchemaDrift;
+use rnaapi::sim::{JobTiming, RunningSim, SimState, Simulator};
+use rnaapi::transport::Transport;
+use rnaapi::{KeyPlacement, NaClient, NaClientBuilder, RetryPolicy};
+use std::sync::Arc;
+
+/// The key every test client sends, with a space and an `&` so that
+/// it has to be encoded in a query
+pub const KEY: &str = "test key&0123456789";
+
+/// The one setting a test is about, `()` for none
+pub trait Setting {
+    fn apply(self, builder: NaClientBuilder) -> NaClientBuilder;
+}
+
+impl Setting for () {
+    fn apply(self, builder: NaClientBuilder) -> NaClientBuilder {
+        builder
+    }
+}
+
+impl Setting for RetryPolicy {
+    fn apply(self, builder: NaClientBuilder) -> NaClientBuilder {
+        builder.retry_policy(self)
+    }
+}
+
+impl Setting for KeyPlacement {
+    fn apply(self, builder: NaClientBuilder) -> NaClientBuilder {
+        builder.key_placement(self)
+    }
+}
+
+impl Setting for SchemaDrift {
+    fn apply(self, builder: NaClientBuilder) -> NaClientBuilder {
+        builder.schema_drift(self)
+    }
+}
+
+/// A builder for a client that sends `KEY` through `transport` instead
+/// of the network, and does not retry
+pub fn builder(transport: Arc<dyn Transport>) -> NaClientBuilder {
+    NaClient::builder(KEY, "https://vapi2.netactuate.com/api/")
+        .retry_policy(RetryPolicy::none())
+        .transport(transport)
+}
+
+/// The client of `builder`, with `setting` applied
+pub fn client(
+    transport: Arc<dyn Transport>, setting: impl Setting,
+) -> NaClient {
+    setting.apply(builder(transport)).build().expect("client")
+}
+
+/// `state` served by a stateful simulator on a free port, sending
+/// `DEFAULT_PAGE_SIZE` items to a page, and a client for it that does
+/// not retry
+pub async fn sim_with(state: SimState) -> (RunningSim, NaClient) {
+    let running = Simulator::new(state)
+        .with_api_key(KEY)
+        .stateful(JobTiming::default())
+        .spawn()
+        .await
+        .expect("sim");
+    let client = NaClient::builder(KEY, running.address())
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .expect("client");
+    (running, client)
+}
@@ -0,0 +1,260 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! Contract tests, every derived endpoint against recorded responses
+//!
+//! The fixtures in `tests/fixtures` are re-recorded against the real API
+//! with `rnatui --record rnaapi_lib/tests/fixtures get ...`. When the API
+//! and a struct drift apart the test for it fails: a missing or retyped
+//! field fails to decode, a new field shows up as `unknown` in
+//! `Drift::between`, the same check the client runs with `SchemaDrift`
+use rnaapi::drift::Drift;
+use rnaapi::endpoints::{
+    Details, IPv4, IPv6, Image, Invoices, Location, MonthlyBw, Package, Record,
+    SSHKeys, Server, Sizes, SrvJob, SrvStatus, Zone,
+};
+use rnaapi::endpoints::{
+    IPv4GetAllParams, IPv6GetAllParams, MonthlyBwGetAllParams,
+    RecordGetAllParams, RecordGetOneParams, SSHKeysGetOneParams,
+    ServerGetOneParams, SizesGetAllParams, SrvJobGetAllParams,
+    SrvJobGetOneParams, SrvStatusGetOneParams, ZoneGetAllParams,
+    ZoneGetOneParams,
+};
+use rnaapi::fixtures::ReplayTransport;
+use rnaapi::{
+    EndpointGetAll, EndpointGetOne, JobId, LocationId, MbPkgId, RecordId,
+    SshKeyId, ZoneId, ZoneType,
+};
+use serde::Serialize;
+use serde_json::Value;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+mod common;
+
+fn fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+fn replay() -> Arc<ReplayTransport> {
+    Arc::new(ReplayTransport::load(fixtures_dir()).expect("fixtures"))
+}
+
+/// The `data` the API sent for `path`
+fn recorded(path: &str) -> Value {
+    let replay = replay();
+    let fixture = replay
+        .fixture(&reqwest::Method::GET, path)
+        .unwrap_or_else(|| panic!("no fixture for {path}"));
+    fixture.body.as_ref().expect("json body")["data"].clone()
+}
+
+/// Everything the API sent for `path` must have a place in the struct,
+/// the same comparison `SchemaDrift` makes. Fields the API left out are
+/// fine here, a required one would already have failed to decode
+fn assert_no_drift(path: &str, kept: &Value) {
+    let sent = recorded(path);
+    if let (Value::Array(sent), Value::Array(kept)) = (&sent, kept) {
+        assert_eq!(sent.len(), kept.len(), "{path} length");
+    }
+    let drift = Drift::between(&sent, kept);
+    assert!(drift.unknown.is_empty(), "{path}: {drift:?}");
+}
+
+async fn get_one<T>(params: T::Params, path: &str)
+where
+    T: EndpointGetOne + Send,
+    T::Endpoint: Serialize,
+{
+    let result = T::get_one(&common::client(replay(), ()), params)
+        .await
+        .unwrap_or_else(|e| panic!("{path}: {e}"));
+    let kept = serde_json::to_value(result).expect("serialize");
+    assert_no_drift(path, &kept);
+}
+
+async fn get_all<T>(params: T::Params, path: &str)
+where
+    T: EndpointGetAll + Send,
+    T::Endpoint: Serialize,
+{
+    let results = T::get_all(&common::client(replay(), ()), params)
+        .await
+        .unwrap_or_else(|e| panic!("{path}: {e}"));
+    assert!(!results.is_empty(), "{path} fixture has no items");
+    let kept = serde_json::to_value(results).expect("serialize");
+    assert_no_drift(path, &kept);
+}
+
+#[tokio::test]
+async fn server() {
+    get_all::<Server>((), "cloud/servers").await;
+    get_one::<Server>(
+        ServerGetOneParams {
+            mbpkgid: MbPkgId(7001),
+        },
+        "cloud/server?mbpkgid=7001",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn srv_status() {
+    get_one::<SrvStatus>(
+        SrvStatusGetOneParams {
+            mbpkgid: MbPkgId(7001),
+        },
+        "cloud/status/7001",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn srv_job() {
+    get_all::<SrvJob>(
+        SrvJobGetAllParams {
+            mbpkgid: MbPkgId(7001),
+        },
+        "cloud/server/7001/jobs",
+    )
+    .await;
+    get_one::<SrvJob>(
+        SrvJobGetOneParams {
+            mbpkgid: MbPkgId(7001),
+            job_id: JobId(90001),
+        },
+        "cloud/server/7001/jobs/90001",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn ipv4() {
+    get_all::<IPv4>(
+        IPv4GetAllParams {
+            mbpkgid: MbPkgId(7001),
+        },
+        "cloud/ipv4?mbpkgid=7001",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn ipv6() {
+    get_all::<IPv6>(
+        IPv6GetAllParams {
+            mbpkgid: MbPkgId(7001),
+        },
+        "cloud/ipv6?mbpkgid=7001",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn monthly_bw() {
+    get_all::<MonthlyBw>(
+        MonthlyBwGetAllParams {
+            mbpkgid: MbPkgId(7001),
+        },
+        "cloud/servermonthlybw/7001",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn zone() {
+    get_all::<Zone>(ZoneGetAllParams::default(), "dns/zones").await;
+    get_all::<Zone>(
+        ZoneGetAllParams {
+            zone_type: Some(ZoneType::Native),
+        },
+        "dns/zones?type=NATIVE",
+    )
+    .await;
+    get_one::<Zone>(
+        ZoneGetOneParams {
+            zone_id: ZoneId(801),
+        },
+        "dns/zone/801",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn record() {
+    get_all::<Record>(
+        RecordGetAllParams {
+            zone_id: ZoneId(801),
+        },
+        "dns/records/801",
+    )
+    .await;
+    get_one::<Record>(
+        RecordGetOneParams {
+            record_id: RecordId(8101),
+        },
+        "dns/record/8101",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn ssh_keys() {
+    get_all::<SSHKeys>((), "account/ssh_keys").await;
+    get_one::<SSHKeys>(
+        SSHKeysGetOneParams {
+            key_id: SshKeyId(301),
+        },
+        "account/ssh_key/301",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn invoices() {
+    get_all::<Invoices>((), "account/invoices").await;
+}
+
+#[tokio::test]
+async fn details() {
+    get_one::<Details>((), "account/details").await;
+}
+
+#[tokio::test]
+async fn image() {
+    get_all::<Image>((), "cloud/images").await;
+}
+
+#[tokio::test]
+async fn location() {
+    get_all::<Location>((), "cloud/locations").await;
+}
+
+#[tokio::test]
+async fn sizes() {
+    get_all::<Sizes>(
+        SizesGetAllParams {
+            location_id: LocationId(3),
+        },
+        "cloud/sizes/3",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn package() {
+    get_all::<Package>((), "cloud/packages").await;
+}
+
+#[tokio::test]
+async fn unrecorded_path_is_not_found() {
+    let err = Server::get_one(
+        &common::client(replay(), ()),
+        ServerGetOneParams {
+            mbpkgid: MbPkgId(1),
+        },
+    )
+    .await
+    .unwrap_err();
+    assert!(err.is_not_found(), "{err}");
+}
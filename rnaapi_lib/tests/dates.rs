@@ -0,0 +1,105 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! The API's date formats decode to chrono types, unset ones to `None`
+use chrono::{NaiveDate, NaiveDateTime};
+use rnaapi::dates;
+use rnaapi::endpoints::{Details, Image, Invoices, MonthlyBw};
+use rnaapi::fixtures::Fixture;
+use serde_json::json;
+
+fn at(text: &str) -> NaiveDateTime {
+    NaiveDateTime::parse_from_str(text, dates::DATETIME_FORMAT).unwrap()
+}
+
+#[test]
+fn unset_dates_are_none() {
+    for unset in ["", "  ", "0000-00-00 00:00:00", "0000-00-00"] {
+        assert_eq!(dates::parse_datetime(unset).unwrap(), None, "{unset:?}");
+        assert_eq!(dates::parse_date(unset).unwrap(), None, "{unset:?}");
+    }
+    assert!(dates::parse_datetime("yesterday").is_err());
+    assert!(dates::parse_date("2025-13-01").is_err());
+}
+
+#[test]
+fn image_timestamps() {
+    let image = json!({
+        "id": 1, "os": null, "description": null, "size": null,
+        "subtype": null, "created": "", "category": null, "updated": null,
+        "iso": null, "bits": null, "tech": null, "icon": null, "private": 0,
+    });
+    let image: Image = serde_json::from_value(image).unwrap();
+    assert_eq!((image.created, image.updated), (None, None));
+
+    let image = json!({"id": 2, "created": "2023-06-12 10:00:00"});
+    let image: Image = serde_json::from_value(image).unwrap();
+    assert_eq!(image.created, Some(at("2023-06-12 10:00:00")));
+    assert_eq!(image.updated, None);
+    let back = serde_json::to_value(&image).unwrap();
+    assert_eq!(back["created"], "2023-06-12 10:00:00");
+}
+
+#[test]
+fn invoice_dates() {
+    let invoice = json!({
+        "id": 5003, "userid": 1001, "date": "2025-10-01",
+        "duedate": "2025-10-15", "datepaid": "0000-00-00 00:00:00",
+        "subtotal": "40.00", "credit": "0.00", "status": "Unpaid",
+        "paymentmethod": "stripe",
+    });
+    let invoice: Invoices = serde_json::from_value(invoice).unwrap();
+    assert_eq!(invoice.duedate, NaiveDate::from_ymd_opt(2025, 10, 15));
+    assert_eq!(invoice.datepaid, None);
+    assert_eq!(invoice.created_at, None);
+
+    let bad = json!({
+        "id": 5003, "userid": 1001, "date": "01/10/2025", "duedate": "",
+        "subtotal": "40.00", "credit": "0.00", "status": "Unpaid",
+        "paymentmethod": "stripe",
+    });
+    let err = serde_json::from_value::<Invoices>(bad).unwrap_err();
+    assert!(err.to_string().contains("01/10/2025"), "{err}");
+
+    let paid = json!({
+        "id": 5001, "userid": 1001, "date": "2025-08-01",
+        "duedate": "2025-08-15", "datepaid": "2025-08-03 11:20:45",
+        "created_at": "2025-08-01 00:00:12",
+        "updated_at": "2025-08-03 11:20:45", "subtotal": "40.00",
+        "credit": "0.00", "status": "Paid", "paymentmethod": "stripe",
+    });
+    let invoice: Invoices = serde_json::from_value(paid).unwrap();
+    assert_eq!(invoice.datepaid, Some(at("2025-08-03 11:20:45")));
+    assert_eq!(invoice.created_at, Some(at("2025-08-01 00:00:12")));
+    assert_eq!(invoice.updated_at, invoice.datepaid);
+
+    // like the timestamps, a date that is not sent is not set
+    let undated = json!({
+        "id": 5004, "userid": 1001, "subtotal": "40.00", "credit": "0.00",
+        "status": "Draft", "paymentmethod": "stripe",
+    });
+    let invoice: Invoices = serde_json::from_value(undated).unwrap();
+    assert_eq!((invoice.date, invoice.duedate), (None, None));
+    assert_eq!(invoice.datepaid, None);
+}
+
+#[test]
+fn bandwidth_month() {
+    let bw: MonthlyBw =
+        serde_json::from_value(json!({"date": "2025-09", "rx": 1, "tx": 2}))
+            .unwrap();
+    assert_eq!(bw.date, NaiveDate::from_ymd_opt(2025, 9, 1).unwrap());
+    assert_eq!(serde_json::to_value(&bw).unwrap()["date"], "2025-09");
+}
+
+#[test]
+fn last_login() {
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/get_account_details.json"
+    );
+    let fixture = Fixture::load(path.as_ref()).unwrap();
+    let details: Details =
+        serde_json::from_value(fixture.body.unwrap()["data"].clone()).unwrap();
+    assert_eq!(details.last_login(), Some(at("2025-10-14 09:12:00")));
+}
@@ -0,0 +1,144 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! Unknown fields are kept in `WithExtra`, drift is collected per
+//! endpoint
+use futures::TryStreamExt;
+use rnaapi::EndpointGetAll;
+use rnaapi::drift::{Drift, SchemaDrift, WithExtra};
+use rnaapi::endpoints::{Image, Server};
+use rnaapi::fixtures::{Fixture, ReplayTransport};
+use serde_json::{Value, json};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+mod common;
+
+fn fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+/// The recorded images with fields added to each, one a list, and
+/// `icon` dropped from the first
+fn drifted() -> ReplayTransport {
+    let mut replay = ReplayTransport::load(fixtures_dir()).expect("fixtures");
+    let mut fixture = replay
+        .fixture(&reqwest::Method::GET, "cloud/images")
+        .expect("images")
+        .clone();
+    let body = fixture.body.as_mut().expect("json body");
+    for image in body["data"].as_array_mut().expect("list") {
+        image["license"] = json!("GPL");
+        image["mirrors"] = json!([{"url": "https://mirror"}]);
+    }
+    body["data"][0].as_object_mut().unwrap().remove("icon");
+    replay.insert(fixture);
+    replay
+}
+
+fn fields(fields: &[&str]) -> BTreeSet<String> {
+    fields.iter().map(|f| f.to_string()).collect()
+}
+
+#[test]
+fn between() {
+    let sent = json!({
+        "id": 1,
+        "a/b": 2,
+        "records": [{"name": "www", "weight": 5}],
+    });
+    let kept = json!({
+        "id": 1,
+        "records": [{"name": "www", "prio": null}],
+        "ttl": null,
+    });
+    assert_eq!(
+        Drift::between(&sent, &kept),
+        Drift {
+            unknown: fields(&["/a~1b", "/records/*/weight"]),
+            missing: fields(&["/records/*/prio", "/ttl"]),
+        }
+    );
+    let same = json!([{"id": 1}, {"id": 2}]);
+    assert!(Drift::between(&same, &same).is_empty());
+}
+
+#[test]
+fn extra_is_kept_not_written() {
+    let path = fixtures_dir().join("get_cloud_server_mbpkgid_7001.json");
+    let mut data =
+        Fixture::load(&path).expect("fixture").body.unwrap()["data"].clone();
+    data["tags"] = json!(["web"]);
+    let server: WithExtra<Server> =
+        serde_json::from_value(data.clone()).expect("decode");
+    assert_eq!(server.extra.get("tags"), Some(&json!(["web"])));
+    assert_eq!(server.fqdn, "web1.example.net");
+    let kept = serde_json::to_value(&server).unwrap();
+    assert_eq!(kept.get("tags"), None);
+    assert_eq!(kept.get("extra"), None);
+    assert_eq!(kept, serde_json::to_value(&server.item).unwrap());
+
+    // the plain struct drops them
+    let plain: Server = serde_json::from_value(data).expect("decode");
+    assert_eq!(plain, server.item);
+}
+
+#[tokio::test]
+async fn collected_per_endpoint() {
+    let client = common::client(Arc::new(drifted()), SchemaDrift::Collect);
+    let images = Image::get_all(&client, ()).await.expect("images");
+    assert_eq!(images[0].icon, None);
+    let kept: Vec<WithExtra<Image>> = client
+        .get_all_as("Image", "cloud/images")
+        .await
+        .expect("images");
+    assert_eq!(kept[1].extra["license"], Value::from("GPL"));
+    assert_eq!(kept[1].item, images[1]);
+
+    let report = client.drift_report();
+    assert_eq!(report.len(), 1);
+    assert_eq!(
+        report["Image"],
+        Drift {
+            unknown: fields(&["/license", "/mirrors"]),
+            missing: fields(&["/icon"]),
+        }
+    );
+    // clones share the report
+    assert_eq!(client.clone().drift_report(), report);
+}
+
+#[tokio::test]
+async fn collected_from_streams() {
+    let client = common::client(Arc::new(drifted()), SchemaDrift::Log);
+    let images: Vec<Image> = Image::stream_all(&client, ())
+        .try_collect()
+        .await
+        .expect("images");
+    assert_eq!(images.len(), 3);
+    assert_eq!(
+        client.drift_report()["Image"].unknown,
+        fields(&["/license", "/mirrors"])
+    );
+}
+
+#[tokio::test]
+async fn ignored_by_default() {
+    let client = common::client(Arc::new(drifted()), SchemaDrift::Ignore);
+    Image::get_all(&client, ()).await.expect("images");
+    assert!(client.drift_report().is_empty());
+
+    let client = client.with_schema_drift(SchemaDrift::Collect);
+    Image::get_all(&client, ()).await.expect("images");
+    assert!(!client.drift_report().is_empty());
+}
+
+#[tokio::test]
+async fn recorded_responses_match() {
+    let replay = ReplayTransport::load(fixtures_dir()).expect("fixtures");
+    let client = common::client(Arc::new(replay), SchemaDrift::Collect);
+    Image::get_all(&client, ()).await.expect("images");
+    Server::get_all(&client, ()).await.expect("servers");
+    assert!(client.drift_report().is_empty());
+}
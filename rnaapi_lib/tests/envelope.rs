@@ -0,0 +1,71 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! Responses decode typed from the body, errors still point at the field
+use rnaapi::endpoints::{Record, RecordGetAllParams};
+use rnaapi::errors::NaApiError;
+use rnaapi::transport::MockTransport;
+use rnaapi::{EndpointGetAll, ZoneId};
+use serde_json::json;
+use std::sync::Arc;
+
+mod common;
+
+fn records(data: serde_json::Value) -> serde_json::Value {
+    json!({"code": 200, "data": data})
+}
+
+#[tokio::test]
+async fn mismatch_points_into_the_body() {
+    let record = json!({
+        "id": 8101, "domain_id": 801, "name": "web1.example.net",
+        "type": "A", "content": "192.0.2.10", "ttl": 3600, "prio": null,
+    });
+    let mut bad = record.clone();
+    bad["id"] = json!("not a number");
+    let mock = MockTransport::new()
+        .get("dns/records/801", records(json!([record, bad])));
+    let params = RecordGetAllParams {
+        zone_id: ZoneId(801),
+    };
+    let err = Record::get_all(&common::client(Arc::new(mock), ()), params)
+        .await
+        .unwrap_err();
+    match err {
+        NaApiError::Deserialize {
+            endpoint, pointer, ..
+        } => {
+            assert_eq!(endpoint, "Record");
+            assert_eq!(pointer, "/data/1/id");
+        }
+        other => panic!("{other}"),
+    }
+}
+
+#[tokio::test]
+async fn api_error_envelope() {
+    let mock = MockTransport::new().get(
+        "dns/records/801",
+        json!({"code": "404", "message": "zone not found"}),
+    );
+    let params = RecordGetAllParams {
+        zone_id: ZoneId(801),
+    };
+    let err = Record::get_all(&common::client(Arc::new(mock), ()), params)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, NaApiError::Api { code: 404, ref message, .. } if message == "zone not found"),
+        "{err}"
+    );
+}
+
+#[tokio::test]
+async fn untyped_data_keeps_null() {
+    let mock = MockTransport::new().get("dns/zone/1", json!({"data": null}));
+    let data = common::client(Arc::new(mock), ())
+        .get_data("dns/zone/1")
+        .await
+        .expect("data");
+    assert!(data.is_null());
+}
@@ -0,0 +1,128 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! Request bodies: the form encoding rules and JSON sent as is
+use reqwest::Method;
+use rnaapi::RequestBody;
+use rnaapi::errors::NaApiError;
+use rnaapi::transport::MockTransport;
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+mod common;
+
+fn pairs(body: &impl Serialize) -> Vec<(String, String)> {
+    match RequestBody::form(body).expect("form") {
+        RequestBody::Form(pairs) => pairs,
+        other => panic!("expected a form, got {other:?}"),
+    }
+}
+
+fn pair(name: &str, value: &str) -> (String, String) {
+    (name.to_owned(), value.to_owned())
+}
+
+#[test]
+fn scalars() {
+    #[derive(Serialize)]
+    struct Body {
+        name: &'static str,
+        ttl: u32,
+        weight: f32,
+    }
+    let body = Body {
+        name: "a b&c",
+        ttl: 300,
+        weight: 0.5,
+    };
+    assert_eq!(
+        pairs(&body),
+        [
+            pair("name", "a b&c"),
+            pair("ttl", "300"),
+            pair("weight", "0.5")
+        ]
+    );
+}
+
+#[test]
+fn bools_are_numbers() {
+    let body = json!({"on": true, "off": false});
+    assert_eq!(pairs(&body), [pair("off", "0"), pair("on", "1")]);
+}
+
+#[test]
+fn sequences_repeat_the_key() {
+    let body = json!({"ssh_key_ids": [301, 302], "tags": []});
+    assert_eq!(
+        pairs(&body),
+        [pair("ssh_key_ids[]", "301"), pair("ssh_key_ids[]", "302")]
+    );
+}
+
+#[test]
+fn none_is_left_out() {
+    #[derive(Serialize)]
+    struct Body {
+        name: &'static str,
+        prio: Option<u32>,
+        ids: Vec<Option<u32>>,
+    }
+    let body = Body {
+        name: "www",
+        prio: None,
+        ids: vec![Some(1), None],
+    };
+    // keys come out sorted, not in field order
+    assert_eq!(pairs(&body), [pair("ids[]", "1"), pair("name", "www")]);
+}
+
+#[test]
+fn nested_objects_are_rejected() {
+    for body in [
+        json!({"soa": {"ttl": 1}}),
+        json!({"records": [{"name": "www"}]}),
+        json!({"matrix": [[1]]}),
+    ] {
+        match RequestBody::form(&body) {
+            Err(NaApiError::UnknownError(message)) => {
+                assert!(message.contains("can not be nested"), "{message}")
+            }
+            other => panic!("{body} should not encode, got {other:?}"),
+        }
+    }
+    // the body itself has to be an object
+    assert!(RequestBody::form(&[1, 2]).is_err());
+    assert!(RequestBody::form("name").is_err());
+}
+
+#[tokio::test]
+async fn json_goes_through_unchanged() {
+    let sent = json!({
+        "name": "www",
+        "enabled": true,
+        "records": [{"type": "A", "content": "192.0.2.10", "prio": null}],
+    });
+    let mock = Arc::new(MockTransport::new().on(
+        Method::POST,
+        "dns/zone",
+        json!({"code": 200, "data": {"id": 801}}),
+    ));
+    let client = common::client(mock.clone(), ());
+    let body = RequestBody::json(&sent).expect("json");
+    let data: Value = client.post_data("dns/zone", body).await.expect("post");
+    assert_eq!(data, json!({"id": 801}));
+
+    let requests = mock.requests();
+    assert_eq!(requests[0].body, RequestBody::Json(sent.clone()));
+    // and what reqwest would put on the wire reads back the same
+    let RequestBody::Json(value) = &requests[0].body else {
+        panic!("expected json");
+    };
+    let wire = serde_json::to_vec(value).expect("encode");
+    assert_eq!(
+        serde_json::from_slice::<Value>(&wire).expect("decode"),
+        sent
+    );
+}
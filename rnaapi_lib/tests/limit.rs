@@ -0,0 +1,119 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! The rate limit and the concurrency cap hold across clones, on paused
+//! time
+use async_trait::async_trait;
+use futures::future::join_all;
+use reqwest::StatusCode;
+use rnaapi::errors::NaApiError;
+use rnaapi::transport::{HttpRequest, HttpResponse, Transport};
+use rnaapi::{NaClient, RateLimit};
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+mod common;
+
+/// Takes `latency` to answer, keeps track of what was in flight when
+#[derive(Debug)]
+struct Slow {
+    latency: Duration,
+    in_flight: AtomicUsize,
+    most_in_flight: AtomicUsize,
+    started: Mutex<Vec<Instant>>,
+}
+
+impl Slow {
+    fn new(latency: Duration) -> Arc<Self> {
+        Arc::new(Slow {
+            latency,
+            in_flight: AtomicUsize::new(0),
+            most_in_flight: AtomicUsize::new(0),
+            started: Mutex::new(Vec::new()),
+        })
+    }
+}
+
+#[async_trait]
+impl Transport for Slow {
+    async fn execute(
+        &self, _request: HttpRequest,
+    ) -> Result<HttpResponse, NaApiError> {
+        self.started.lock().unwrap().push(Instant::now());
+        let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.most_in_flight.fetch_max(now, Ordering::SeqCst);
+        tokio::time::sleep(self.latency).await;
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+        Ok(HttpResponse::json(StatusCode::OK, &json!({"data": []})))
+    }
+}
+
+/// `clones` copies of one client, each sending `each` requests at once
+async fn fan_out(client: NaClient, clones: usize, each: usize) {
+    let calls = (0..clones).flat_map(|_| {
+        let client = client.clone();
+        (0..each).map(move |_| {
+            let client = client.clone();
+            async move { client.get_data("cloud/servers").await }
+        })
+    });
+    for result in join_all(calls).await {
+        result.expect("response");
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn clones_share_the_rate() {
+    let transport = Slow::new(Duration::ZERO);
+    let limit = RateLimit::per_second(5);
+    let start = Instant::now();
+    let client = common::builder(transport.clone()).rate_limit(limit);
+    fan_out(client.build().expect("client"), 4, 5).await;
+
+    let started = transport.started.lock().unwrap().clone();
+    assert_eq!(started.len(), 20);
+    // a burst of 5, then one every 200ms
+    for (i, at) in started.iter().enumerate() {
+        let earliest = Duration::from_millis(200 * (i as u64 + 1))
+            .saturating_sub(Duration::from_secs(1));
+        let at = at.duration_since(start) + Duration::from_millis(1);
+        assert!(at >= earliest, "request {i} went out at {at:?}");
+    }
+    assert!(start.elapsed() >= Duration::from_secs(3));
+}
+
+#[tokio::test(start_paused = true)]
+async fn burst_can_be_smaller_than_the_rate() {
+    let transport = Slow::new(Duration::ZERO);
+    let limit = RateLimit::per_minute(60).burst(1);
+    let start = Instant::now();
+    let client = common::builder(transport.clone()).rate_limit(limit);
+    fan_out(client.build().expect("client"), 2, 2).await;
+    // one right away, then one a second
+    assert!(start.elapsed() >= Duration::from_secs(3));
+    assert!(start.elapsed() < Duration::from_secs(4));
+}
+
+#[tokio::test(start_paused = true)]
+async fn clones_share_the_concurrency_cap() {
+    let transport = Slow::new(Duration::from_millis(100));
+    let start = Instant::now();
+    let client = common::builder(transport.clone()).max_concurrency(2);
+    fan_out(client.build().expect("client"), 5, 2).await;
+    assert_eq!(transport.most_in_flight.load(Ordering::SeqCst), 2);
+    assert_eq!(transport.started.lock().unwrap().len(), 10);
+    // ten requests, two at a time
+    assert!(start.elapsed() >= Duration::from_millis(500));
+}
+
+#[tokio::test(start_paused = true)]
+async fn unlimited_by_default() {
+    let transport = Slow::new(Duration::from_millis(100));
+    let start = Instant::now();
+    fan_out(common::client(transport.clone(), ()), 5, 2).await;
+    assert_eq!(transport.most_in_flight.load(Ordering::SeqCst), 10);
+    assert_eq!(start.elapsed(), Duration::from_millis(100));
+}
@@ -0,0 +1,170 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! Derived create, update and delete, including answers without data
+use reqwest::Method;
+use rnaapi::endpoints::{
+    Record, RecordDeleteParams, RecordRequest, RecordUpdateParams,
+    SSHKeyRequest, SSHKeys, SSHKeysDeleteParams,
+};
+use rnaapi::errors::NaApiError;
+use rnaapi::transport::MockTransport;
+use rnaapi::{
+    EndpointCreate, EndpointDelete, EndpointUpdate, RecordId, RequestBody,
+    SshKeyId, ZoneId,
+};
+use serde_json::json;
+use std::sync::Arc;
+
+mod common;
+
+fn record_request() -> RecordRequest {
+    RecordRequest {
+        domain_id: ZoneId(801),
+        name: "www.example.net".to_owned(),
+        record_type: "A".to_owned(),
+        content: "192.0.2.20".to_owned(),
+        ttl: Some(300),
+        prio: None,
+    }
+}
+
+fn record(content: &str) -> serde_json::Value {
+    json!({
+        "code": 200,
+        "data": {
+            "id": 8103, "domain_id": 801, "name": "www.example.net",
+            "type": "A", "content": content, "ttl": 300, "prio": null,
+        },
+    })
+}
+
+fn form(body: &RequestBody) -> Vec<(String, String)> {
+    match body {
+        RequestBody::Form(pairs) => pairs.clone(),
+        other => panic!("expected a form, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn record_create_update_delete() {
+    let mock = Arc::new(
+        MockTransport::new()
+            .on(Method::POST, "dns/record", record("192.0.2.20"))
+            .on(Method::PUT, "dns/record/8103", record("192.0.2.21"))
+            .on(
+                Method::DELETE,
+                "dns/record/8103",
+                json!({"code": 200, "message": "Record deleted"}),
+            ),
+    );
+    let client = common::client(mock.clone(), ());
+
+    let created = Record::create(&client, (), &record_request())
+        .await
+        .expect("create");
+    assert_eq!(created.id, RecordId(8103));
+    let params = RecordUpdateParams {
+        record_id: created.id,
+    };
+    let mut request = record_request();
+    request.content = "192.0.2.21".to_owned();
+    let updated = Record::update(&client, params, &request)
+        .await
+        .expect("update");
+    assert_eq!(updated.content, "192.0.2.21");
+    let params = RecordDeleteParams {
+        record_id: created.id,
+    };
+    Record::delete(&client, params).await.expect("delete");
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[0].method, Method::POST);
+    let sent = form(&requests[0].body);
+    assert!(sent.contains(&("type".to_owned(), "A".to_owned())));
+    assert!(sent.contains(&("ttl".to_owned(), "300".to_owned())));
+    // `None` is left out rather than sent empty
+    assert!(!sent.iter().any(|(name, _)| name == "prio"));
+    assert_eq!(requests[1].method, Method::PUT);
+    assert_eq!(requests[2].method, Method::DELETE);
+    assert_eq!(requests[2].body, RequestBody::Empty);
+}
+
+#[tokio::test]
+async fn ssh_key_create_delete() {
+    let key = json!({
+        "id": 302, "name": "laptop", "ssh_key": "ssh-ed25519 AAAA",
+        "fingerprint": "SHA256:abc",
+    });
+    let mock = Arc::new(
+        MockTransport::new()
+            .on(Method::POST, "account/ssh_key", json!({"data": key}))
+            // no code, no data, just the message
+            .on(
+                Method::DELETE,
+                "account/ssh_key/302",
+                json!({"message": "Key removed"}),
+            ),
+    );
+    let client = common::client(mock.clone(), ());
+    let request = SSHKeyRequest {
+        name: "laptop".to_owned(),
+        ssh_key: "ssh-ed25519 AAAA".to_owned(),
+    };
+    let created = SSHKeys::create(&client, (), &request)
+        .await
+        .expect("create");
+    assert_eq!(created.id, SshKeyId(302));
+    let params = SSHKeysDeleteParams { key_id: created.id };
+    SSHKeys::delete(&client, params).await.expect("delete");
+}
+
+#[tokio::test]
+async fn error_code_in_a_success_is_still_an_error() {
+    let mock = Arc::new(MockTransport::new().on(
+        Method::DELETE,
+        "account/ssh_key/302",
+        json!({"code": "404", "message": "no such key"}),
+    ));
+    let params = SSHKeysDeleteParams {
+        key_id: SshKeyId(302),
+    };
+    match SSHKeys::delete(&common::client(mock.clone(), ()), params).await {
+        Err(NaApiError::Api { code, message, .. }) => {
+            assert_eq!(code, 404);
+            assert_eq!(message, "no such key");
+        }
+        other => panic!("expected an API error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn untyped_changes_without_data_are_null() {
+    let mock = Arc::new(MockTransport::new().on(
+        Method::PATCH,
+        "dns/zone/801",
+        json!({"code": 200, "message": "Zone updated"}),
+    ));
+    let client = common::client(mock.clone(), ());
+    let data = client
+        .patch_data("dns/zone/801", RequestBody::Empty)
+        .await
+        .expect("patch");
+    assert!(data.is_null());
+
+    // a typed create needs what it was asked for
+    let mock = Arc::new(MockTransport::new().on(
+        Method::POST,
+        "dns/record",
+        json!({"code": 200, "message": "Record added"}),
+    ));
+    let err = Record::create(
+        &common::client(mock.clone(), ()),
+        (),
+        &record_request(),
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, NaApiError::UnknownError(_)), "{err}");
+}
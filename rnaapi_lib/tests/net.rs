@@ -0,0 +1,117 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! Address fields decode to network types, garbage is refused
+use rnaapi::endpoints::{IPv4, IPv6, Server};
+use rnaapi::fixtures::Fixture;
+use rnaapi::net::{MacAddr, Netmask, PrefixLen};
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+fn recorded<T: DeserializeOwned>(file: &str) -> T {
+    let path = format!("{}/tests/fixtures/{file}", env!("CARGO_MANIFEST_DIR"));
+    let fixture = Fixture::load(path.as_ref()).expect("fixture");
+    serde_json::from_value(fixture.body.expect("json")["data"].clone())
+        .expect("decode")
+}
+
+fn v4(text: &str) -> Ipv4Addr {
+    text.parse().unwrap()
+}
+
+fn v6(text: &str) -> Ipv6Addr {
+    text.parse().unwrap()
+}
+
+#[test]
+fn ipv4_subnet() {
+    let ips: Vec<IPv4> = recorded("get_cloud_ipv4_mbpkgid_7001.json");
+    let ip = &ips[0];
+    assert_eq!(ip.netmask.prefix_len(), 24);
+    assert_eq!(ip.network().to_string(), "192.0.2.0/24");
+    let mut hosts = ip.hosts();
+    assert_eq!(hosts.next(), Some(v4("192.0.2.1")));
+    assert_eq!(hosts.next_back(), Some(v4("192.0.2.254")));
+    assert!(ip.contains(&ip.gateway));
+    assert!(!ip.contains(&v4("192.0.3.1")));
+    assert_eq!(
+        serde_json::to_value(ip).unwrap()["netmask"],
+        "255.255.255.0"
+    );
+}
+
+#[test]
+fn ipv6_subnet() {
+    let ips: Vec<IPv6> = recorded("get_cloud_ipv6_mbpkgid_7001.json");
+    let ip = &ips[0];
+    assert_eq!(ip.netmask, PrefixLen::new(64).unwrap());
+    assert_eq!(ip.broadcast, None);
+    assert_eq!(ip.network().to_string(), "2001:db8:10::/64");
+    assert!(ip.contains(&v6("2001:db8:10::ffff")));
+    assert!(!ip.contains(&v6("2001:db8:11::1")));
+    assert_eq!(serde_json::to_value(ip).unwrap()["netmask"], "64");
+}
+
+#[test]
+fn server_subnet() {
+    let server: Server = recorded("get_cloud_server_mbpkgid_7001.json");
+    let ipv4: Vec<IPv4> = recorded("get_cloud_ipv4_mbpkgid_7001.json");
+    let ipv6: Vec<IPv6> = recorded("get_cloud_ipv6_mbpkgid_7001.json");
+    let mac: MacAddr = "52:54:00:12:34:56".parse().unwrap();
+    assert_eq!(server.nic1_mac, Some(mac));
+    assert!(server.in_subnet(IpAddr::V4(v4("192.0.2.77")), &ipv4, &ipv6));
+    assert!(server.in_subnet(IpAddr::V6(v6("2001:db8:10::2")), &ipv4, &ipv6));
+    assert!(!server.in_subnet(IpAddr::V4(v4("198.51.100.20")), &ipv4, &ipv6));
+}
+
+#[test]
+fn unbuilt_servers_have_no_address() {
+    let servers: Vec<Value> = recorded("get_cloud_servers.json");
+    // a recorded server, as the API sends a package that was never built
+    let mut sent = servers[1].clone();
+    for field in ["ip", "ipv6", "nic1_mac", "nic2_mac"] {
+        sent[field] = json!("");
+    }
+    sent["status"] = json!("CANCELLED");
+    let cancelled: Server = serde_json::from_value(sent).expect("decode");
+    assert_eq!(cancelled.ip, None);
+    assert_eq!(cancelled.nic1_mac, None);
+    assert!(!cancelled.in_subnet(IpAddr::V4(v4("192.0.2.10")), &[], &[]));
+    // written back the way the API sends it
+    let sent = serde_json::to_value(&cancelled).unwrap();
+    assert_eq!(sent["ip"], "");
+    assert_eq!(sent["nic1_mac"], "");
+    let built: Server = serde_json::from_value(servers[0].clone()).unwrap();
+    assert_eq!(built.ip, Some(v4("192.0.2.10")));
+}
+
+#[test]
+fn garbage_is_refused() {
+    let ip = json!({
+        "id": 1, "primary": 1, "reverse": "", "ip": "192.0.2.10",
+        "netmask": "255.255.255.0", "gateway": "192.0.2.1",
+        "broadcast": "192.0.2.255",
+    });
+    let with = |key: &str, value: &str| {
+        let mut ip = ip.clone();
+        ip[key] = Value::from(value);
+        serde_json::from_value::<IPv4>(ip)
+    };
+    assert!(with("ip", "192.0.2.10").is_ok());
+    assert!(with("ip", "192.0.2.300").is_err());
+    assert!(with("gateway", "").is_err());
+    let err = with("netmask", "255.0.255.0").unwrap_err();
+    assert!(err.to_string().contains("invalid netmask"), "{err}");
+
+    assert!("52:54:00:12:34".parse::<MacAddr>().is_err());
+    assert!("52:54:00:12:34:zz".parse::<MacAddr>().is_err());
+    assert!("52:54:00:12:34:56:78".parse::<MacAddr>().is_err());
+    assert_eq!(
+        "52-54-00-12-34-56".parse::<MacAddr>().unwrap().to_string(),
+        "52:54:00:12:34:56"
+    );
+    assert!("129".parse::<PrefixLen>().is_err());
+    assert_eq!(Netmask::new(0).unwrap().addr(), v4("0.0.0.0"));
+    assert_eq!(Netmask::new(32).unwrap().addr(), v4("255.255.255.255"));
+}
@@ -0,0 +1,114 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! Numbers decode the same from JSON numbers and strings, money is exact
+use rnaapi::Money;
+use rnaapi::endpoints::{Details, Image, Invoices, Sizes, Zone};
+use rnaapi::fixtures::Fixture;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+fn recorded<T: DeserializeOwned>(file: &str) -> T {
+    let path = format!("{}/tests/fixtures/{file}", env!("CARGO_MANIFEST_DIR"));
+    let fixture = Fixture::load(path.as_ref()).expect("fixture");
+    serde_json::from_value(fixture.body.expect("json")["data"].clone())
+        .expect("decode")
+}
+
+fn zone(ttl: Value) -> Result<Zone, serde_json::Error> {
+    serde_json::from_value(json!({
+        "id": 801, "name": "example.com", "type": "NATIVE",
+        "master": null, "ttl": ttl, "soa": null, "records": null, "ns": null,
+    }))
+}
+
+#[test]
+fn zone_ttl_either_way() {
+    assert_eq!(zone(json!("3600")).unwrap().ttl, Some(3600));
+    assert_eq!(zone(json!(3600)).unwrap().ttl, Some(3600));
+    assert_eq!(zone(json!(" 3600 ")).unwrap().ttl, Some(3600));
+    assert_eq!(zone(json!("")).unwrap().ttl, None);
+    assert_eq!(zone(json!(null)).unwrap().ttl, None);
+    assert!(zone(json!("an hour")).is_err());
+    assert!(zone(json!(-1)).is_err());
+    assert!(zone(json!(true)).is_err());
+}
+
+#[test]
+fn recorded_zones() {
+    let one: Zone = recorded("get_dns_zone_801.json");
+    let all: Vec<Zone> = recorded("get_dns_zones_type_NATIVE.json");
+    assert_eq!(one.ttl, Some(3600));
+    assert_eq!(all[0].ttl, Some(3600));
+    let soa = one.soa.expect("soa");
+    assert_eq!(soa.serial, 2025101401);
+    assert_eq!(soa.default_ttl, 3600);
+}
+
+#[test]
+fn recorded_sizes() {
+    let sizes: Vec<Sizes> = recorded("get_cloud_sizes_3.json");
+    assert_eq!(sizes[0].ram_mb, 1024);
+    assert_eq!(sizes[0].disk_gb, 25);
+    assert_eq!(sizes[1].transfer_gb, 2000);
+    assert_eq!(sizes[1].port_mbps, 1000);
+    assert_eq!(sizes[1].price, Money::from_cents(1000));
+    let sent = serde_json::to_value(&sizes[0]).unwrap();
+    assert_eq!(sent["ram"], 1024);
+    assert_eq!(sent["price"], "5.00");
+}
+
+#[test]
+fn recorded_money() {
+    let invoices: Vec<Invoices> = recorded("get_account_invoices.json");
+    let total: Money = invoices.iter().map(|i| i.subtotal).sum();
+    assert_eq!(total.to_string(), "125.00");
+    let credit: Money = invoices.iter().map(|i| i.credit).sum();
+    assert_eq!(total - credit, Money::from_cents(12000));
+
+    let details: Details = recorded("get_account_details.json");
+    assert_eq!(details.credit, Some("12.50".parse().unwrap()));
+
+    let images: Vec<Image> = recorded("get_cloud_images.json");
+    assert_eq!(images[2].private, Some(1));
+}
+
+#[test]
+fn money() {
+    let from_number: Money = serde_json::from_value(json!(0.1)).unwrap();
+    let from_string: Money = serde_json::from_value(json!("0.2")).unwrap();
+    assert_eq!((from_number + from_string).to_string(), "0.30");
+    let from_int: Money = serde_json::from_value(json!(40)).unwrap();
+    assert_eq!(serde_json::to_value(from_int).unwrap(), "40.00");
+
+    // hourly prices have more places than cents, none are lost
+    let hourly: Money = serde_json::from_value(json!("0.0137")).unwrap();
+    assert_eq!(hourly.to_string(), "0.01");
+    let sent = serde_json::to_value(hourly).unwrap();
+    assert_eq!(sent, "0.0137");
+    assert_eq!(serde_json::from_value::<Money>(sent).unwrap(), hourly);
+    let from_float: Money = serde_json::from_value(json!(1.2345)).unwrap();
+    assert_eq!(serde_json::to_value(from_float).unwrap(), "1.2345");
+    assert!(serde_json::from_value::<Money>(json!("4O.00")).is_err());
+    assert!(serde_json::from_value::<Money>(json!("")).is_err());
+    assert!(serde_json::from_value::<Money>(json!(null)).is_err());
+}
+
+#[test]
+fn details_without_credit() {
+    let mut details: Value = recorded("get_account_details.json");
+    for credit in [json!(""), json!(null)] {
+        details["credit"] = credit;
+        let decoded: Details = serde_json::from_value(details.clone()).unwrap();
+        assert_eq!(decoded.credit, None);
+    }
+    details.as_object_mut().unwrap().remove("credit");
+    let decoded: Details = serde_json::from_value(details.clone()).unwrap();
+    assert_eq!(decoded.credit, None);
+
+    details["credit"] = json!("0.00");
+    let decoded: Details = serde_json::from_value(details.clone()).unwrap();
+    assert_eq!(decoded.credit, Some(Money::from_cents(0)));
+    details["credit"] = json!("none");
+    assert!(serde_json::from_value::<Details>(details).is_err());
+}
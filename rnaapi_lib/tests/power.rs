@@ -0,0 +1,79 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! Boot, shutdown and reboot hand back a job that can be followed
+use rnaapi::endpoints::{
+    Server, SrvJob, SrvJobGetOneParams, SrvStatus, SrvStatusGetOneParams,
+};
+use rnaapi::errors::NaApiError;
+use rnaapi::sim::{Action, SimState};
+use rnaapi::{EndpointGetOne, JobStatus, MbPkgId, NaClient, ServerState};
+use std::time::Duration;
+
+mod common;
+use common::sim_with;
+
+const MBPKGID: MbPkgId = MbPkgId(7001);
+
+async fn state(client: &NaClient) -> ServerState {
+    let params = SrvStatusGetOneParams { mbpkgid: MBPKGID };
+    SrvStatus::get_one(client, params)
+        .await
+        .expect("status")
+        .status
+}
+
+async fn job(client: &NaClient, job: &SrvJob) -> SrvJob {
+    let params = SrvJobGetOneParams {
+        mbpkgid: MBPKGID,
+        job_id: job.id,
+    };
+    SrvJob::get_one(client, params).await.expect("job")
+}
+
+#[tokio::test]
+async fn shutdown_then_boot() {
+    let (running, client) = sim_with(SimState::default_seed()).await;
+    let shutdown = Server::shutdown(&client, MBPKGID).await.expect("shutdown");
+    assert_eq!(shutdown.command, "shutdown");
+    assert_eq!(shutdown.status, JobStatus::Queued);
+
+    running.advance(Duration::from_secs(10));
+    assert!(job(&client, &shutdown).await.status.is_running());
+    assert_eq!(state(&client).await, ServerState::ShuttingDown);
+
+    running.advance(Duration::from_secs(60));
+    assert_eq!(job(&client, &shutdown).await.status, JobStatus::Done);
+    assert!(state(&client).await.is_stopped());
+
+    let boot = Server::boot(&client, MBPKGID).await.expect("boot");
+    assert_eq!(boot.command, "boot");
+    assert_ne!(boot.id, shutdown.id);
+    running.advance(Duration::from_secs(60));
+    assert_eq!(job(&client, &boot).await.status, JobStatus::Done);
+    assert!(state(&client).await.is_running());
+}
+
+#[tokio::test]
+async fn reboot_can_fail() {
+    let (running, client) = sim_with(SimState::default_seed()).await;
+    running.fail_next(Action::Reboot);
+    let reboot = Server::reboot(&client, MBPKGID).await.expect("reboot");
+    assert_eq!(reboot.command, "reboot");
+    running.advance(Duration::from_secs(60));
+    assert!(job(&client, &reboot).await.status.is_failed());
+}
+
+#[tokio::test]
+async fn busy_or_missing_servers_are_errors() {
+    let (_running, client) = sim_with(SimState::default_seed()).await;
+    Server::reboot(&client, MBPKGID).await.expect("reboot");
+    match Server::shutdown(&client, MBPKGID).await {
+        Err(NaApiError::HttpStatus { status, path, .. }) => {
+            assert_eq!(status, 409);
+            assert_eq!(path, "cloud/server/shutdown/7001");
+        }
+        other => panic!("expected a conflict, got {other:?}"),
+    }
+    assert!(Server::boot(&client, MbPkgId(1)).await.is_err());
+}
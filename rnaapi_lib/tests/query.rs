@@ -0,0 +1,64 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! Declared query params end up URL encoded after the path
+use rnaapi::endpoints::{IPv4, IPv4GetAllParams, Zone, ZoneGetAllParams};
+use rnaapi::transport::MockTransport;
+use rnaapi::{EndpointGetAll, MbPkgId, NaClient, ZoneType};
+use serde_json::json;
+use std::sync::Arc;
+
+mod common;
+
+#[tokio::test]
+async fn optional_filter() {
+    let mock = Arc::new(
+        MockTransport::new()
+            .get("dns/zones", json!({"data": []}))
+            .get("dns/zones?type=MASTER", json!({"data": []})),
+    );
+    let client = common::client(mock.clone(), ());
+    Zone::get_all(&client, ZoneGetAllParams::default())
+        .await
+        .expect("all zones");
+    let master = ZoneGetAllParams {
+        zone_type: Some(ZoneType::Master),
+    };
+    Zone::get_all(&client, master).await.expect("master zones");
+
+    let paths: Vec<_> = mock.requests().into_iter().map(|r| r.path).collect();
+    assert_eq!(paths, ["dns/zones", "dns/zones?type=MASTER"]);
+}
+
+#[tokio::test]
+async fn values_and_key_are_encoded() {
+    let mock = Arc::new(MockTransport::new());
+    let client = common::client(mock.clone(), ());
+    let zone_type = Some(ZoneType::Unknown("A&B =C".to_owned()));
+    let _ = Zone::get_all(&client, ZoneGetAllParams { zone_type }).await;
+    let params = IPv4GetAllParams {
+        mbpkgid: MbPkgId(7001),
+    };
+    let _ = IPv4::get_all(&client, params).await;
+
+    let requests = mock.requests();
+    assert_eq!(requests[0].path, "dns/zones?type=A%26B+%3DC");
+    assert!(
+        requests[0]
+            .url
+            .ends_with("?type=A%26B+%3DC&key=test+key%260123456789"),
+        "{}",
+        requests[0].url
+    );
+    assert_eq!(requests[1].path, "cloud/ipv4?mbpkgid=7001");
+}
+
+#[test]
+fn with_query() {
+    let none: [(&str, Option<u32>); 1] = [("page", None)];
+    assert_eq!(NaClient::with_query("a", &none).unwrap(), "a");
+    assert_eq!(
+        NaClient::with_query("a?b=1", &[("c", 2)]).unwrap(),
+        "a?b=1&c=2"
+    );
+}
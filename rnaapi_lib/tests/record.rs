@@ -0,0 +1,62 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! Recording writes fixtures the replay side can read back, key scrubbed
+use rnaapi::EndpointGetAll;
+use rnaapi::endpoints::Server;
+use rnaapi::fixtures::{Fixture, ReplayTransport};
+use rnaapi::secret::REDACTED;
+use rnaapi::transport::MockTransport;
+use serde_json::json;
+use std::sync::Arc;
+
+mod common;
+use common::KEY;
+
+#[tokio::test]
+async fn record_then_replay() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    // a real recorded server, with the key showing up in the body
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/get_cloud_servers.json"
+    );
+    let mut body = Fixture::load(path.as_ref()).expect("fixture").body.unwrap();
+    body["data"][0]["fqdn"] = json!(format!("{KEY}.example.net"));
+    let mock = MockTransport::new().get("cloud/servers", body);
+    let client = common::builder(Arc::new(mock))
+        .record_fixtures(dir.path())
+        .build()
+        .expect("client");
+    let live = Server::get_all(&client, ()).await.expect("live");
+    assert_eq!(live[0].fqdn, format!("{KEY}.example.net"));
+
+    let file = dir.path().join("get_cloud_servers.json");
+    let text = std::fs::read_to_string(&file).expect("fixture written");
+    assert!(!text.contains(KEY), "key leaked into {text}");
+    assert!(text.contains(REDACTED));
+    let fixture = Fixture::load(&file).expect("fixture");
+    assert_eq!(fixture.path, "cloud/servers");
+    assert_eq!(fixture.status, 200);
+
+    let replay = ReplayTransport::load(dir.path()).expect("replay");
+    assert_eq!(replay.len(), 1);
+    let client = common::client(Arc::new(replay), ());
+    let replayed = Server::get_all(&client, ()).await.expect("replayed");
+    assert_eq!(replayed.len(), live.len());
+    assert_eq!(replayed[0].fqdn, format!("{REDACTED}.example.net"));
+    assert_eq!(replayed[1], live[1]);
+}
+
+#[test]
+fn file_names() {
+    let get = reqwest::Method::GET;
+    assert_eq!(
+        Fixture::file_name(&get, "cloud/server?mbpkgid=7001"),
+        "get_cloud_server_mbpkgid_7001.json"
+    );
+    assert_eq!(
+        Fixture::file_name(&reqwest::Method::DELETE, "/dns/record/8101/"),
+        "delete_dns_record_8101.json"
+    );
+}
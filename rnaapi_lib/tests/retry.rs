@@ -0,0 +1,216 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! Transient failures are retried with backoff, on paused time
+use reqwest::header::{HeaderValue, RETRY_AFTER};
+use reqwest::{Method, StatusCode};
+use rnaapi::errors::NaApiError;
+use rnaapi::transport::{HttpResponse, MockTransport};
+use rnaapi::{RequestBody, RetryPolicy};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+
+mod common;
+
+const PATH: &str = "cloud/servers";
+
+fn policy() -> RetryPolicy {
+    RetryPolicy::default()
+        .base_delay(Duration::from_millis(100))
+        .max_delay(Duration::from_secs(10))
+}
+
+fn ok() -> serde_json::Value {
+    json!({"code": 200, "data": []})
+}
+
+fn rate_limited(retry_after: &'static str) -> HttpResponse {
+    let mut response = HttpResponse::json(
+        StatusCode::TOO_MANY_REQUESTS,
+        &json!({"message": "slow down"}),
+    );
+    response
+        .headers
+        .insert(RETRY_AFTER, HeaderValue::from_static(retry_after));
+    response
+}
+
+#[tokio::test(start_paused = true)]
+async fn server_errors_back_off() {
+    let mock = Arc::new(
+        MockTransport::new()
+            .status(PATH, 503, json!({"message": "busy"}))
+            .status(PATH, 502, json!({"message": "busy"}))
+            .get(PATH, ok()),
+    );
+    let start = Instant::now();
+    common::client(mock.clone(), policy())
+        .get_data(PATH)
+        .await
+        .expect("third try");
+    assert_eq!(mock.requests().len(), 3);
+    // half fixed, half jitter: 50..=100ms then 100..=200ms
+    let waited = start.elapsed();
+    assert!(waited >= Duration::from_millis(150), "{waited:?}");
+    assert!(waited <= Duration::from_millis(300), "{waited:?}");
+}
+
+#[tokio::test(start_paused = true)]
+async fn gives_up_after_max_attempts() {
+    let mock =
+        Arc::new(MockTransport::new().status(PATH, 500, json!({"code": 500})));
+    let err = common::client(mock.clone(), policy())
+        .get_data(PATH)
+        .await
+        .unwrap_err();
+    assert!(err.is_server_error(), "{err}");
+    assert_eq!(mock.requests().len(), 3);
+}
+
+#[tokio::test(start_paused = true)]
+async fn rate_limits_wait_as_asked() {
+    let mock = Arc::new(
+        MockTransport::new()
+            .respond(Method::GET, PATH, rate_limited("7"))
+            .get(PATH, ok()),
+    );
+    let start = Instant::now();
+    common::client(mock.clone(), policy())
+        .get_data(PATH)
+        .await
+        .expect("second try");
+    assert_eq!(start.elapsed(), Duration::from_secs(7));
+    assert_eq!(mock.requests().len(), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn retry_after_past_max_delay_is_not_waited_for() {
+    let mock = Arc::new(
+        MockTransport::new()
+            .respond(Method::GET, PATH, rate_limited("86400"))
+            .get(PATH, ok()),
+    );
+    let start = Instant::now();
+    match common::client(mock.clone(), policy()).get_data(PATH).await {
+        Err(NaApiError::HttpStatus {
+            status: 429,
+            retry_after,
+            ..
+        }) => assert_eq!(retry_after, Some(Duration::from_secs(86400))),
+        other => panic!("expected the 429, got {other:?}"),
+    }
+    assert_eq!(start.elapsed(), Duration::ZERO);
+    assert_eq!(mock.requests().len(), 1);
+
+    // without honoring it the usual backoff applies
+    let mock = Arc::new(
+        MockTransport::new()
+            .respond(Method::GET, PATH, rate_limited("86400"))
+            .get(PATH, ok()),
+    );
+    let policy = policy().honor_retry_after(false);
+    common::client(mock.clone(), policy)
+        .get_data(PATH)
+        .await
+        .expect("second try");
+    assert!(start.elapsed() <= Duration::from_millis(100));
+}
+
+#[tokio::test(start_paused = true)]
+async fn connection_errors_are_retried() {
+    let mock = Arc::new(
+        MockTransport::new()
+            .fail(Method::GET, PATH, "connection reset")
+            .get(PATH, ok()),
+    );
+    common::client(mock.clone(), policy())
+        .get_data(PATH)
+        .await
+        .expect("second try");
+    assert_eq!(mock.requests().len(), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn client_errors_are_not_retried() {
+    let mock =
+        Arc::new(MockTransport::new().status(PATH, 404, json!({"code": 404})));
+    common::client(mock.clone(), policy())
+        .get_data(PATH)
+        .await
+        .unwrap_err();
+    assert_eq!(mock.requests().len(), 1);
+}
+
+/// A POST that is busy once, then goes through
+fn busy_then_ok(path: &str) -> Arc<MockTransport> {
+    let busy = HttpResponse::json(
+        StatusCode::SERVICE_UNAVAILABLE,
+        &json!({"message": "busy"}),
+    );
+    let mock = MockTransport::new().respond(Method::POST, path, busy).on(
+        Method::POST,
+        path,
+        ok(),
+    );
+    Arc::new(mock)
+}
+
+#[tokio::test(start_paused = true)]
+async fn mutations_only_when_asked() {
+    let path = "dns/record";
+    let mock = busy_then_ok(path);
+    let err = common::client(mock.clone(), policy())
+        .post_data(path, RequestBody::Empty)
+        .await
+        .unwrap_err();
+    assert!(err.is_server_error(), "{err}");
+    assert_eq!(mock.requests().len(), 1);
+
+    let mock = busy_then_ok(path);
+    common::client(mock.clone(), policy().retry_mutations(true))
+        .post_data(path, RequestBody::Empty)
+        .await
+        .expect("second try");
+    assert_eq!(mock.requests().len(), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn idempotent_retries_one_mutation() {
+    let path = "dns/record";
+    let mock = busy_then_ok(path);
+    let client = common::client(mock.clone(), policy());
+    client
+        .idempotent()
+        .post_data(path, RequestBody::Empty)
+        .await
+        .expect("second try");
+    assert_eq!(mock.requests().len(), 2);
+
+    // the client it came from still sends a mutation once
+    let mock = busy_then_ok(path);
+    let client = common::client(mock.clone(), policy());
+    let _ = client.idempotent();
+    client
+        .post_data(path, RequestBody::Empty)
+        .await
+        .unwrap_err();
+    assert_eq!(mock.requests().len(), 1);
+}
+
+/// A 2xx carrying a rate limit `code` is not retried, but says so
+#[tokio::test(start_paused = true)]
+async fn rate_limit_code_in_a_success_is_not_retried() {
+    let mock = Arc::new(MockTransport::new().on(
+        Method::GET,
+        PATH,
+        json!({"code": 429, "message": "slow down"}),
+    ));
+    let err = common::client(mock.clone(), policy())
+        .get_data(PATH)
+        .await
+        .unwrap_err();
+    assert!(err.is_rate_limited(), "{err}");
+    assert_eq!(mock.requests().len(), 1);
+}
@@ -0,0 +1,116 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! The API key never shows up in errors, `Debug` output or, with a
+//! header placement, the url
+use reqwest::Method;
+use reqwest::header::AUTHORIZATION;
+use rnaapi::errors::NaApiError;
+use rnaapi::transport::MockTransport;
+use rnaapi::{KeyPlacement, NaClient, RetryPolicy};
+use serde_json::json;
+use std::sync::Arc;
+
+mod common;
+use common::KEY;
+
+fn assert_hidden(e: &NaApiError) {
+    assert!(!format!("{e}").contains(KEY), "{e}");
+    assert!(!format!("{e:?}").contains(KEY), "{e:?}");
+}
+
+#[tokio::test]
+async fn transport_errors() {
+    // nothing listens on port 1, reqwest puts the url in its error
+    let client = NaClient::builder(KEY, "http://127.0.0.1:1/api/")
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .expect("client");
+    let err = client.get_data("cloud/servers").await.unwrap_err();
+    assert!(err.is_transport(), "{err}");
+    assert_hidden(&err);
+    // the url is still there to debug with, minus the key
+    assert!(format!("{err:?}").contains("cloud/servers?key=%5BREDACTED%5D"));
+}
+
+#[tokio::test]
+async fn http_status_errors() {
+    let mock = Arc::new(MockTransport::new().status(
+        "cloud/servers",
+        401,
+        json!({"message": format!("key {KEY} is not valid")}),
+    ));
+    let err = common::client(mock.clone(), KeyPlacement::Query)
+        .get_data("cloud/servers")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, NaApiError::HttpStatus { status: 401, .. }));
+    assert_hidden(&err);
+}
+
+#[tokio::test]
+async fn api_errors() {
+    let mock = Arc::new(MockTransport::new().get(
+        "cloud/servers",
+        json!({"code": 403, "message": format!("{KEY} may not do that")}),
+    ));
+    let err = common::client(mock.clone(), KeyPlacement::Query)
+        .get_data("cloud/servers")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, NaApiError::Api { code: 403, .. }));
+    assert_hidden(&err);
+}
+
+#[tokio::test]
+async fn debug_output() {
+    let mock = Arc::new(
+        MockTransport::new().get("cloud/servers", json!({"data": []})),
+    );
+    let client = common::client(mock.clone(), KeyPlacement::Query);
+    assert!(!format!("{client:?}").contains(KEY));
+    client.get_data("cloud/servers").await.expect("servers");
+
+    let request = &mock.requests()[0];
+    // the real url carries it, its Debug does not
+    let url = reqwest::Url::parse(&request.url).expect("url");
+    assert!(
+        url.query_pairs()
+            .any(|(name, key)| name == "key" && key == KEY)
+    );
+    let debug = format!("{request:?}");
+    assert!(!debug.contains(KEY), "{debug}");
+    assert!(debug.contains("cloud/servers"));
+}
+
+#[tokio::test]
+async fn header_placement_keeps_it_out_of_the_url() {
+    let mock = Arc::new(MockTransport::new().on(
+        Method::GET,
+        "cloud/servers",
+        json!({"data": []}),
+    ));
+    let placements = [
+        KeyPlacement::Header("X-API-Key".to_owned()),
+        KeyPlacement::Bearer,
+    ];
+    for placement in placements {
+        common::client(mock.clone(), placement)
+            .get_data("cloud/servers")
+            .await
+            .expect("servers");
+    }
+
+    let requests = mock.requests();
+    for request in &requests {
+        let url = reqwest::Url::parse(&request.url).expect("url");
+        assert!(!url.query_pairs().any(|(name, _)| name == "key"), "{url}");
+        assert!(!request.url.contains(KEY));
+        assert!(!format!("{request:?}").contains(KEY));
+    }
+    assert_eq!(requests[0].headers["x-api-key"], KEY);
+    assert_eq!(
+        requests[1].headers[AUTHORIZATION],
+        format!("Bearer {KEY}").as_str()
+    );
+}
@@ -0,0 +1,58 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! Known states decode to variants, new ones to `Unknown` without failing
+use rnaapi::endpoints::{SrvJob, SrvStatus, Zone};
+use rnaapi::{JobStatus, PackageStatus, ServerState, ServerStatus, ZoneType};
+use serde_json::json;
+
+#[test]
+fn unknown_values_survive() {
+    let status: SrvStatus =
+        serde_json::from_value(json!({"status": "MIGRATING"})).unwrap();
+    assert_eq!(status.status, ServerState::Unknown("MIGRATING".to_owned()));
+    assert!(!status.status.is_running() && !status.status.is_busy());
+    assert_eq!(
+        serde_json::to_value(&status).unwrap()["status"],
+        "MIGRATING"
+    );
+
+    let job = json!({"id": 1, "ts_insert": "", "command": "boot", "status": 7});
+    let job: SrvJob = serde_json::from_value(job).unwrap();
+    assert_eq!(job.status, JobStatus::Unknown(7));
+    assert!(!job.status.is_terminal());
+    assert_eq!(serde_json::to_value(&job).unwrap()["status"], 7);
+}
+
+#[test]
+fn known_values() {
+    assert!(ServerState::from("RUNNING").is_running());
+    assert!(ServerState::from("stopped").is_stopped());
+    assert!(ServerState::from("REBOOTING").is_busy());
+    assert!(ServerStatus::from("ACTIVE").is_active());
+    assert_eq!(PackageStatus::from("active"), PackageStatus::Active);
+    assert_eq!(PackageStatus::Active.to_string(), "Active");
+
+    let zone = json!({"id": 801, "name": "example.net", "type": "NATIVE"});
+    let zone: Zone = serde_json::from_value(zone).unwrap();
+    assert_eq!(zone.zone_type, ZoneType::Native);
+}
+
+#[test]
+fn job_status_helpers() {
+    let statuses: Vec<JobStatus> = (0..=3).map(JobStatus::from).collect();
+    assert_eq!(
+        statuses,
+        [
+            JobStatus::Queued,
+            JobStatus::Running,
+            JobStatus::Done,
+            JobStatus::Failed
+        ]
+    );
+    assert!(JobStatus::Running.is_running());
+    assert!(JobStatus::Done.is_terminal() && !JobStatus::Done.is_failed());
+    assert!(JobStatus::Failed.is_terminal() && JobStatus::Failed.is_failed());
+    assert!(!JobStatus::Queued.is_terminal());
+    assert_eq!(JobStatus::Failed.code(), 3);
+}
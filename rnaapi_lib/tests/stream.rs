@@ -0,0 +1,98 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! `stream_all` hands out items as they are parsed and follows pages
+use futures::{StreamExt, TryStreamExt};
+use rnaapi::endpoints::{Invoices, Record, RecordGetAllParams};
+use rnaapi::errors::NaApiError;
+use rnaapi::sim::{RunningSim, SimState};
+use rnaapi::transport::MockTransport;
+use rnaapi::{EndpointGetAll, NaClient, ZoneId};
+use serde_json::json;
+use std::sync::Arc;
+
+mod common;
+use common::{KEY, sim_with};
+
+/// A simulator with `count` invoices, sent `DEFAULT_PAGE_SIZE` (100)
+/// to a page
+async fn sim(count: u32) -> (RunningSim, NaClient) {
+    let mut state = SimState::default_seed();
+    let invoice = state.invoices[0].clone();
+    state.invoices = (1..=count)
+        .map(|id| Invoices {
+            id,
+            ..invoice.clone()
+        })
+        .collect();
+    sim_with(state).await
+}
+
+#[tokio::test]
+async fn follows_pages() {
+    let (_sim, client) = sim(250).await;
+    let streamed: Vec<Invoices> = Invoices::stream_all(&client, ())
+        .try_collect()
+        .await
+        .expect("stream");
+    let ids: Vec<u32> = streamed.iter().map(|i| i.id).collect();
+    assert_eq!(ids, (1..=250).collect::<Vec<_>>());
+
+    let all = Invoices::get_all(&client, ()).await.expect("get_all");
+    assert_eq!(all, streamed);
+}
+
+#[tokio::test]
+async fn unpaged_and_early_drop() {
+    let (_sim, client) = sim(3).await;
+    let first: Vec<_> =
+        Invoices::stream_all(&client, ()).take(2).collect().await;
+    assert_eq!(first.len(), 2);
+    assert_eq!(first[1].as_ref().expect("invoice").id, 2);
+    let all = Invoices::get_all(&client, ()).await.expect("get_all");
+    assert_eq!(all.len(), 3);
+}
+
+#[tokio::test]
+async fn items_before_a_bad_one_still_arrive() {
+    let record = json!({
+        "id": 8101, "domain_id": 801, "name": "web1.example.net",
+        "type": "A", "content": "192.0.2.10", "ttl": 3600, "prio": null,
+    });
+    let mut bad = record.clone();
+    bad["id"] = json!("not a number");
+    let body = json!({"code": 200, "data": [record, bad]});
+    let client = common::client(
+        Arc::new(MockTransport::new().get("dns/records/801", body)),
+        (),
+    );
+    let params = RecordGetAllParams {
+        zone_id: ZoneId(801),
+    };
+    let items: Vec<_> = Record::stream_all(&client, params).collect().await;
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].as_ref().expect("first").name, "web1.example.net");
+    match &items[1] {
+        Err(NaApiError::Deserialize { pointer, .. }) => {
+            assert_eq!(pointer, "/data/1/id")
+        }
+        other => panic!("{other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn api_error_ends_the_stream() {
+    let body = json!({"code": 401, "message": format!("bad key {KEY}")});
+    let client = common::client(
+        Arc::new(MockTransport::new().get("account/invoices", body)),
+        (),
+    );
+    let items: Vec<_> = Invoices::stream_all(&client, ()).collect().await;
+    match &items[..] {
+        [Err(NaApiError::Api { code, message, .. })] => {
+            assert_eq!(*code, 401);
+            assert!(!message.contains(KEY), "{message}");
+        }
+        other => panic!("{other:?}"),
+    }
+}
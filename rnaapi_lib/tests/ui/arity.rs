@@ -0,0 +1,9 @@
+use rnaapi::EndpointGetOne;
+
+#[derive(EndpointGetOne)]
+#[getone(path = "cloud/server/{}/jobs/{}", params(mbpkgid: MbPkgId))]
+pub struct Job {
+    pub command: String,
+}
+
+fn main() {}
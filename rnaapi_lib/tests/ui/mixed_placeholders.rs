@@ -0,0 +1,12 @@
+use rnaapi::EndpointGetOne;
+
+#[derive(EndpointGetOne)]
+#[getone(
+    path = "cloud/server/{mbpkgid}/jobs/{}",
+    params(mbpkgid: MbPkgId, job_id: JobId)
+)]
+pub struct Job {
+    pub command: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use rnaapi::EndpointGetOne;
+
+#[derive(EndpointGetOne)]
+#[getone(path = "cloud/status/{mbpkgid}", params(zone_id: ZoneId))]
+pub struct Status {
+    pub status: String,
+}
+
+fn main() {}
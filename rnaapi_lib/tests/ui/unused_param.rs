@@ -0,0 +1,9 @@
+use rnaapi::EndpointGetAll;
+
+#[derive(EndpointGetAll)]
+#[getall(path = "cloud/servers", params(mbpkgid: MbPkgId))]
+pub struct Servers {
+    pub fqdn: String,
+}
+
+fn main() {}
@@ -23,41 +23,85 @@
 //! ## A single servers info
 //! `rnaapi -m <mbpkgid>`
 //!
+//! ### Recording fixtures
+//! `rnatui --record rnaapi_lib/tests/fixtures get server -m <mbpkgid>`
+//! writes each response to a file, API key scrubbed, for the contract
+//! tests in rnaapi_lib
+//!
+//! ### Power actions
+//! `rnatui server boot|shutdown|reboot -m <mbpkgid>` starts the job and
+//! prints it, follow it with `rnatui get job -m <mbpkgid> -j <jobid>`
+//!
+//! ### Building servers
+//! `rnatui server build -l <location> -p <plan> -o <os> -n <hostname>`
+//! orders a new package and builds it, `-m <mbpkgid>` builds on one you
+//! have. Add keys with `-k <keyid>`, cloud-init data with `-u <file>`.
+//! The request is checked against the catalog before it is sent
+//!
+//! ### Checking for API changes
+//! `rnatui schema-check` calls every GET endpoint and lists the fields the
+//! API sent that the structs don't have, and the other way round. With
+//! `-f rnaapi_lib/tests/fixtures` it checks recorded responses instead
+//!
 //! That's it.
 //!
 // Copyright (C) 2025 Dennis Durling
 // This file is part of RNAAPI Rust API Client Library, licensed
 // under the GNU General Public License v3.0
 use anyhow::Result;
-use chrono::NaiveDate;
 use clap::CommandFactory;
 use clap::{Parser, Subcommand};
 use clap_complete::{Shell, generate};
-use rnaapi::NaClient;
 use rnaapi::config::Settings;
+use rnaapi::drift::SchemaDrift;
 use rnaapi::endpoints;
-use rnaapi::{EndpointGetAll, EndpointGetArgs, EndpointGetOne};
+use rnaapi::fixtures::ReplayTransport;
+use rnaapi::{
+    EndpointGetAll, EndpointGetOne, JobId, LocationId, MbPkgId, OsId, PlanId,
+    SshKeyId, ZoneId, ZoneType,
+};
+use rnaapi::{NaClient, RateLimit};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+mod schema;
+
+/// requests per second, across every call this makes
+const RATE_LIMIT: u32 = 5;
+/// requests in flight at once
+const MAX_CONCURRENCY: usize = 4;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     //! Test/Example "main" function, right now it just takes
     //! one argument, `-m <mbpkgid>` if not given, returns all the servers you own
 
-    // Get settings from config
-    let settings = Settings::new()?;
-
     // Defaults
     let mut ssh_keyid: u32 = 0;
     let mut display_count: usize = 0;
     let mut loc_mbpkgid: u32 = 0;
     let mut loc_jobid: u32 = 0;
     let mut loc_zoneid: u32 = 0;
+    let mut loc_zonetype: Option<ZoneType> = None;
     let mut loc_sizes: u32 = 0;
     let mut command: &str = "default";
+    let mut build_request: Option<endpoints::BuildRequest> = None;
 
     // parse our args into args
     let cli = Cli::parse();
 
+    // recorded responses need no key or address
+    let settings = match &cli.cmd {
+        Some(Commands::SchemaCheck {
+            fixtures: Some(_), ..
+        }) => Settings {
+            api_key: "fixtures".into(),
+            api_url: Settings::API_ADDRESS.to_owned(),
+        },
+        // Get settings from config
+        _ => Settings::new()?,
+    };
+
     // check cli sub commands
     match &cli.cmd {
         Some(Commands::GenerateCompletions { shell }) => {
@@ -79,7 +123,8 @@ async fn main() -> Result<()> {
                     command = "server";
                 }
             }
-            GetCommands::Dns { id } => {
+            GetCommands::Dns { id, zone_type, all } => {
+                loc_zonetype = (!*all).then(|| zone_type.clone());
                 if *id >= 1 {
                     loc_zoneid = *id;
                     command = "dns";
@@ -134,40 +179,134 @@ async fn main() -> Result<()> {
                 command = "account";
             }
         },
+        Some(Commands::Server { cmd }) => match cmd {
+            ServerCommands::Boot { mbpkgid } => {
+                loc_mbpkgid = *mbpkgid;
+                command = "boot";
+            }
+            ServerCommands::Shutdown { mbpkgid } => {
+                loc_mbpkgid = *mbpkgid;
+                command = "shutdown";
+            }
+            ServerCommands::Reboot { mbpkgid } => {
+                loc_mbpkgid = *mbpkgid;
+                command = "reboot";
+            }
+            ServerCommands::Build {
+                mbpkgid,
+                location,
+                plan,
+                os,
+                hostname,
+                ssh_keys,
+                user_data,
+            } => {
+                let mut request = endpoints::BuildRequest::new(
+                    LocationId(*location),
+                    PlanId(*plan),
+                    OsId(*os),
+                    hostname,
+                );
+                if let Some(mbpkgid) = mbpkgid {
+                    request = request.on_package(MbPkgId(*mbpkgid));
+                }
+                for key_id in ssh_keys {
+                    request = request.ssh_key(SshKeyId(*key_id));
+                }
+                if let Some(file) = user_data {
+                    request = request.user_data(std::fs::read_to_string(file)?);
+                }
+                build_request = Some(request);
+                command = "build";
+            }
+        },
+        Some(Commands::SchemaCheck { .. }) => {
+            command = "schema-check";
+        }
         _ => {}
     }
     // playing with new constructor for client
     // let na_client = NaClient::new(API_KEY.to_owned(), API_ADDRESS.to_owned()).await;
-    let na_client = NaClient::new(settings.api_key, settings.api_url).await?;
+    // the `server` views fan out with `tokio::join!`, keep that polite
+    let mut builder = NaClient::builder(settings.api_key, settings.api_url)
+        .rate_limit(RateLimit::per_second(RATE_LIMIT))
+        .max_concurrency(MAX_CONCURRENCY);
+    if let Some(dir) = &cli.record {
+        // keep what came back as fixtures, IE for rnaapi_lib/tests
+        builder = builder.record_fixtures(dir);
+    }
+    if let Some(Commands::SchemaCheck { fixtures }) = &cli.cmd {
+        builder = builder.schema_drift(SchemaDrift::Collect);
+        if let Some(dir) = fixtures {
+            builder = builder.transport(Arc::new(ReplayTransport::load(dir)?));
+        }
+    }
+    let na_client = builder.build()?;
 
-    if command == "server" {
+    if command == "schema-check" {
+        schema::check(&na_client).await?;
+    } else if matches!(command, "boot" | "shutdown" | "reboot") {
+        let mbpkgid = MbPkgId(loc_mbpkgid);
+        let job = match command {
+            "boot" => endpoints::Server::boot(&na_client, mbpkgid).await?,
+            "shutdown" => {
+                endpoints::Server::shutdown(&na_client, mbpkgid).await?
+            }
+            _ => endpoints::Server::reboot(&na_client, mbpkgid).await?,
+        };
+        println!(
+            "Job: {}, Status: {}, command: {}",
+            job.id, job.status, job.command
+        );
+    } else if let Some(request) = &build_request {
+        let job = endpoints::Server::build(&na_client, request).await?;
+        println!(
+            "Job: {}, Status: {}, command: {}, mbpkgid: {}",
+            job.id,
+            job.status,
+            job.command,
+            job.mbpkgid.map(|m| m.to_string()).unwrap_or_default()
+        );
+    } else if command == "server" {
         if loc_mbpkgid > 0 {
             // submit jobs to the tokio async runtime
             // this automatically awaits so no need for .await
             let (server, srvjobs, ipv4s, ipv6s, status, bw_usage) = tokio::join!(
                 endpoints::Server::get_one(
                     &na_client,
-                    EndpointGetArgs::OneInt(loc_mbpkgid)
+                    endpoints::ServerGetOneParams {
+                        mbpkgid: MbPkgId(loc_mbpkgid)
+                    }
                 ),
                 endpoints::SrvJob::get_all(
                     &na_client,
-                    EndpointGetArgs::OneInt(loc_mbpkgid)
+                    endpoints::SrvJobGetAllParams {
+                        mbpkgid: MbPkgId(loc_mbpkgid)
+                    }
                 ),
                 endpoints::IPv4::get_all(
                     &na_client,
-                    EndpointGetArgs::OneInt(loc_mbpkgid)
+                    endpoints::IPv4GetAllParams {
+                        mbpkgid: MbPkgId(loc_mbpkgid)
+                    }
                 ),
                 endpoints::IPv6::get_all(
                     &na_client,
-                    EndpointGetArgs::OneInt(loc_mbpkgid)
+                    endpoints::IPv6GetAllParams {
+                        mbpkgid: MbPkgId(loc_mbpkgid)
+                    }
                 ),
                 endpoints::SrvStatus::get_one(
                     &na_client,
-                    EndpointGetArgs::OneInt(loc_mbpkgid)
+                    endpoints::SrvStatusGetOneParams {
+                        mbpkgid: MbPkgId(loc_mbpkgid)
+                    }
                 ),
                 endpoints::MonthlyBw::get_all(
                     &na_client,
-                    EndpointGetArgs::OneInt(loc_mbpkgid),
+                    endpoints::MonthlyBwGetAllParams {
+                        mbpkgid: MbPkgId(loc_mbpkgid)
+                    },
                 ),
             );
 
@@ -185,7 +324,11 @@ async fn main() -> Result<()> {
                 for job in jobs {
                     println!(
                         "Inserted: {}, Status: {}, command: {}",
-                        job.ts_insert, job.status, job.command
+                        job.ts_insert
+                            .map(|ts| ts.to_string())
+                            .unwrap_or_default(),
+                        job.status,
+                        job.command
                     );
                 }
             }
@@ -221,22 +364,18 @@ async fn main() -> Result<()> {
             // print out bandwidth usage
             println!();
             if let Ok(mut bwusage) = bw_usage {
-                bwusage.sort_by_key(|b| {
-                    let date_with_day = format!("{}-01", b.date);
-                    NaiveDate::parse_from_str(&date_with_day, "%Y-%m-%d")
-                        .expect("Failed to parse date")
-                });
+                bwusage.sort_by_key(|b| b.date);
                 for usage in bwusage {
                     println!(
                         "Date: {}, Rx: {}, Tx: {}",
-                        usage.date, usage.rx, usage.tx
+                        usage.date.format("%Y-%m"),
+                        usage.rx,
+                        usage.tx
                     );
                 }
             }
         } else {
-            let srvrs =
-                endpoints::Server::get_all(&na_client, EndpointGetArgs::NoArgs)
-                    .await?;
+            let srvrs = endpoints::Server::get_all(&na_client, ()).await?;
 
             for srvr in srvrs {
                 println!("ID: {}, fqdn: {}", srvr.mbpkgid, srvr.fqdn);
@@ -248,7 +387,9 @@ async fn main() -> Result<()> {
             // // print out the zone name
             let zone = endpoints::Zone::get_one(
                 &na_client,
-                EndpointGetArgs::OneInt(loc_zoneid),
+                endpoints::ZoneGetOneParams {
+                    zone_id: ZoneId(loc_zoneid),
+                },
             )
             .await?;
             println!("Zone: {}", zone.name);
@@ -270,9 +411,13 @@ async fn main() -> Result<()> {
         } else {
             println!();
             // list dns zones
-            let zones =
-                endpoints::Zone::get_all(&na_client, EndpointGetArgs::NoArgs)
-                    .await?;
+            let zones = endpoints::Zone::get_all(
+                &na_client,
+                endpoints::ZoneGetAllParams {
+                    zone_type: loc_zonetype,
+                },
+            )
+            .await?;
             for zone in zones {
                 println!(
                     "ID: {}, Size: {}, Name: {}",
@@ -284,7 +429,9 @@ async fn main() -> Result<()> {
         if ssh_keyid > 0 {
             let sshkey = endpoints::SSHKeys::get_one(
                 &na_client,
-                EndpointGetArgs::OneInt(ssh_keyid),
+                endpoints::SSHKeysGetOneParams {
+                    key_id: SshKeyId(ssh_keyid),
+                },
             )
             .await?;
             println!();
@@ -294,11 +441,7 @@ async fn main() -> Result<()> {
                 sshkey.id, sshkey.name, sshkey.fingerprint
             );
         } else {
-            let keys = endpoints::SSHKeys::get_all(
-                &na_client,
-                EndpointGetArgs::NoArgs,
-            )
-            .await?;
+            let keys = endpoints::SSHKeys::get_all(&na_client, ()).await?;
             println!();
             // print some ssh keys
             for sshkey in keys {
@@ -312,7 +455,10 @@ async fn main() -> Result<()> {
         if loc_mbpkgid > 0 && loc_jobid > 0 {
             let job = endpoints::SrvJob::get_one(
                 &na_client,
-                EndpointGetArgs::TwoInt(loc_mbpkgid, loc_jobid),
+                endpoints::SrvJobGetOneParams {
+                    mbpkgid: MbPkgId(loc_mbpkgid),
+                    job_id: JobId(loc_jobid),
+                },
             )
             .await?;
             println!();
@@ -324,7 +470,9 @@ async fn main() -> Result<()> {
         } else if loc_mbpkgid > 0 {
             let jobs = endpoints::SrvJob::get_all(
                 &na_client,
-                EndpointGetArgs::OneInt(loc_mbpkgid),
+                endpoints::SrvJobGetAllParams {
+                    mbpkgid: MbPkgId(loc_mbpkgid),
+                },
             )
             .await?;
             println!();
@@ -341,11 +489,15 @@ async fn main() -> Result<()> {
             let (ipv4s, ipv6s) = tokio::join!(
                 endpoints::IPv4::get_all(
                     &na_client,
-                    EndpointGetArgs::OneInt(loc_mbpkgid)
+                    endpoints::IPv4GetAllParams {
+                        mbpkgid: MbPkgId(loc_mbpkgid)
+                    }
                 ),
                 endpoints::IPv6::get_all(
                     &na_client,
-                    EndpointGetArgs::OneInt(loc_mbpkgid)
+                    endpoints::IPv6GetAllParams {
+                        mbpkgid: MbPkgId(loc_mbpkgid)
+                    }
                 ),
             );
 
@@ -374,25 +526,23 @@ async fn main() -> Result<()> {
     } else if command == "bandwidth" {
         let mut bw_usage = endpoints::MonthlyBw::get_all(
             &na_client,
-            EndpointGetArgs::OneInt(loc_mbpkgid),
+            endpoints::MonthlyBwGetAllParams {
+                mbpkgid: MbPkgId(loc_mbpkgid),
+            },
         )
         .await?;
         println!();
-        bw_usage.sort_by_key(|b| {
-            let date_with_day = format!("{}-01", b.date);
-            NaiveDate::parse_from_str(&date_with_day, "%Y-%m-%d")
-                .expect("Failed to parse date")
-        });
+        bw_usage.sort_by_key(|b| b.date);
         for usage in bw_usage {
             println!(
                 "Date: {}, Rx: {}, Tx: {}",
-                usage.date, usage.rx, usage.tx
+                usage.date.format("%Y-%m"),
+                usage.rx,
+                usage.tx
             );
         }
     } else if command == "location" {
-        let locs =
-            endpoints::Location::get_all(&na_client, EndpointGetArgs::NoArgs)
-                .await?;
+        let locs = endpoints::Location::get_all(&na_client, ()).await?;
         println!();
         // list locations
         for loc in locs {
@@ -402,9 +552,7 @@ async fn main() -> Result<()> {
             );
         }
     } else if command == "account" {
-        let deets =
-            endpoints::Details::get_one(&na_client, EndpointGetArgs::NoArgs)
-                .await?;
+        let deets = endpoints::Details::get_one(&na_client, ()).await?;
         println!();
         // print acct details
         println!(
@@ -416,9 +564,7 @@ async fn main() -> Result<()> {
             deets.postcode
         );
     } else if command == "image" {
-        let imgs =
-            endpoints::Image::get_all(&na_client, EndpointGetArgs::NoArgs)
-                .await?;
+        let imgs = endpoints::Image::get_all(&na_client, ()).await?;
         println!();
         // list images
         for img in imgs {
@@ -431,9 +577,7 @@ async fn main() -> Result<()> {
         }
         println!();
     } else if command == "invoice" {
-        let invoices =
-            endpoints::Invoices::get_all(&na_client, EndpointGetArgs::NoArgs)
-                .await?;
+        let invoices = endpoints::Invoices::get_all(&na_client, ()).await?;
         // print some of the invoices, say 3?
         for invoice in invoices.iter().take(display_count) {
             println!("ID: {}, Status: {}", invoice.id, invoice.status);
@@ -441,7 +585,9 @@ async fn main() -> Result<()> {
     } else if command == "sizes" {
         let sizes = endpoints::Sizes::get_all(
             &na_client,
-            EndpointGetArgs::OneInt(loc_sizes),
+            endpoints::SizesGetAllParams {
+                location_id: LocationId(loc_sizes),
+            },
         )
         .await?;
         // print some of the invoices, say 3?
@@ -461,6 +607,10 @@ async fn main() -> Result<()> {
 struct Cli {
     #[command(subcommand)]
     cmd: Option<Commands>,
+
+    /// write every response to a fixture file in this directory
+    #[arg(long, global = true)]
+    record: Option<PathBuf>,
 }
 
 #[derive(Subcommand, Debug)]
@@ -469,6 +619,19 @@ enum Commands {
         #[command(subcommand)]
         cmd: GetCommands,
     },
+    /// Server power actions and builds
+    #[command(visible_alias = "srv")]
+    Server {
+        #[command(subcommand)]
+        cmd: ServerCommands,
+    },
+    /// compare live or recorded responses with the library's structs
+    #[command(visible_alias = "schema")]
+    SchemaCheck {
+        /// check the recorded responses in this directory instead
+        #[arg(short, long)]
+        fixtures: Option<PathBuf>,
+    },
     /// generate completions
     #[command(visible_alias = "gen")]
     GenerateCompletions { shell: Shell },
@@ -489,6 +652,13 @@ enum GetCommands {
         // -i argument for picking a dns zone
         #[arg(short, long, default_value_t = 0)]
         id: u32,
+        // -t argument for listing NATIVE, MASTER or SLAVE zones, the
+        // NATIVE ones unless asked
+        #[arg(short = 't', long, default_value_t = ZoneType::Native)]
+        zone_type: ZoneType,
+        // -a argument for listing zones of every type
+        #[arg(short, long, conflicts_with = "zone_type")]
+        all: bool,
     },
 
     /// SSh subcommands
@@ -549,3 +719,46 @@ enum GetCommands {
     /// Account subcommands
     Account {},
 }
+
+#[derive(Subcommand, Debug)]
+enum ServerCommands {
+    /// Power the server on
+    Boot {
+        #[arg(short, long)]
+        mbpkgid: u32,
+    },
+
+    /// Shut the server down
+    Shutdown {
+        #[arg(short, long)]
+        mbpkgid: u32,
+    },
+
+    /// Reboot the server
+    Reboot {
+        #[arg(short, long)]
+        mbpkgid: u32,
+    },
+
+    /// Build a server, on a new package unless one is given
+    Build {
+        /// package to build on, wiping what is there
+        #[arg(short, long)]
+        mbpkgid: Option<u32>,
+        #[arg(short, long)]
+        location: u32,
+        #[arg(short, long)]
+        plan: u32,
+        /// image id, see `get image`
+        #[arg(short, long)]
+        os: u32,
+        #[arg(short = 'n', long)]
+        hostname: String,
+        /// SSH key id, repeat for more
+        #[arg(short = 'k', long = "ssh-key")]
+        ssh_keys: Vec<u32>,
+        /// file handed to cloud-init
+        #[arg(short, long)]
+        user_data: Option<PathBuf>,
+    },
+}
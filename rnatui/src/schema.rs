@@ -0,0 +1,192 @@
+// Copyright (C) 2025 Dennis Durling
+// This file is part of RNAAPI Rust API Client Library, licensed
+// under the GNU General Public License v3.0
+//! `rnatui schema-check`, compares what the API sends with the structs
+//!
+//! Every GET endpoint is called once, ids for the single item calls are
+//! taken from the first item of the matching list. The client collects
+//! the drift, see `rnaapi::drift`, this prints it per endpoint
+use anyhow::{Result, bail};
+use rnaapi::endpoints;
+use rnaapi::errors::NaApiError;
+use rnaapi::{EndpointGetAll, EndpointGetOne, NaClient};
+use std::collections::BTreeMap;
+
+/// Calls that went wrong, by endpoint
+#[derive(Default)]
+struct Failures(BTreeMap<&'static str, NaApiError>);
+
+impl Failures {
+    /// The result, or `None` with the error kept for the report
+    fn keep<T>(
+        &mut self, endpoint: &'static str, result: Result<T, NaApiError>,
+    ) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(e) => {
+                self.0.entry(endpoint).or_insert(e);
+                None
+            }
+        }
+    }
+}
+
+/// Call everything, print the report. Errors out when any struct and
+/// response differ, so it can gate a CI job
+pub async fn check(na_client: &NaClient) -> Result<()> {
+    let mut failures = Failures::default();
+
+    let servers = endpoints::Server::get_all(na_client, ()).await;
+    if let Some(mbpkgid) = failures
+        .keep("Server", servers)
+        .and_then(|servers| servers.first().map(|s| s.mbpkgid))
+    {
+        let server = endpoints::Server::get_one(
+            na_client,
+            endpoints::ServerGetOneParams { mbpkgid },
+        )
+        .await;
+        failures.keep("Server", server);
+        let status = endpoints::SrvStatus::get_one(
+            na_client,
+            endpoints::SrvStatusGetOneParams { mbpkgid },
+        )
+        .await;
+        failures.keep("SrvStatus", status);
+        let ipv4 = endpoints::IPv4::get_all(
+            na_client,
+            endpoints::IPv4GetAllParams { mbpkgid },
+        )
+        .await;
+        failures.keep("IPv4", ipv4);
+        let ipv6 = endpoints::IPv6::get_all(
+            na_client,
+            endpoints::IPv6GetAllParams { mbpkgid },
+        )
+        .await;
+        failures.keep("IPv6", ipv6);
+        let bw = endpoints::MonthlyBw::get_all(
+            na_client,
+            endpoints::MonthlyBwGetAllParams { mbpkgid },
+        )
+        .await;
+        failures.keep("MonthlyBw", bw);
+        let jobs = endpoints::SrvJob::get_all(
+            na_client,
+            endpoints::SrvJobGetAllParams { mbpkgid },
+        )
+        .await;
+        if let Some(job_id) = failures
+            .keep("SrvJob", jobs)
+            .and_then(|jobs| jobs.first().map(|j| j.id))
+        {
+            let job = endpoints::SrvJob::get_one(
+                na_client,
+                endpoints::SrvJobGetOneParams { mbpkgid, job_id },
+            )
+            .await;
+            failures.keep("SrvJob", job);
+        }
+    }
+
+    let zones = endpoints::Zone::get_all(
+        na_client,
+        endpoints::ZoneGetAllParams::default(),
+    )
+    .await;
+    if let Some(zone_id) = failures
+        .keep("Zone", zones)
+        .and_then(|zones| zones.first().map(|z| z.id))
+    {
+        let zone = endpoints::Zone::get_one(
+            na_client,
+            endpoints::ZoneGetOneParams { zone_id },
+        )
+        .await;
+        failures.keep("Zone", zone);
+        let records = endpoints::Record::get_all(
+            na_client,
+            endpoints::RecordGetAllParams { zone_id },
+        )
+        .await;
+        if let Some(record_id) = failures
+            .keep("Record", records)
+            .and_then(|records| records.first().map(|r| r.id))
+        {
+            let record = endpoints::Record::get_one(
+                na_client,
+                endpoints::RecordGetOneParams { record_id },
+            )
+            .await;
+            failures.keep("Record", record);
+        }
+    }
+
+    let keys = endpoints::SSHKeys::get_all(na_client, ()).await;
+    if let Some(key_id) = failures
+        .keep("SSHKeys", keys)
+        .and_then(|keys| keys.first().map(|k| k.id))
+    {
+        let key = endpoints::SSHKeys::get_one(
+            na_client,
+            endpoints::SSHKeysGetOneParams { key_id },
+        )
+        .await;
+        failures.keep("SSHKeys", key);
+    }
+
+    let locations = endpoints::Location::get_all(na_client, ()).await;
+    if let Some(location_id) = failures
+        .keep("Location", locations)
+        .and_then(|locations| locations.first().map(|l| l.id))
+    {
+        let sizes = endpoints::Sizes::get_all(
+            na_client,
+            endpoints::SizesGetAllParams { location_id },
+        )
+        .await;
+        failures.keep("Sizes", sizes);
+    }
+
+    let details = endpoints::Details::get_one(na_client, ()).await;
+    failures.keep("Details", details);
+    let invoices = endpoints::Invoices::get_all(na_client, ()).await;
+    failures.keep("Invoices", invoices);
+    let images = endpoints::Image::get_all(na_client, ()).await;
+    failures.keep("Image", images);
+    let packages = endpoints::Package::get_all(na_client, ()).await;
+    failures.keep("Package", packages);
+
+    let report = na_client.drift_report();
+    let mut drifted = 0;
+    for (endpoint, drift) in &report {
+        // fields left out are listed, but only new ones are a mismatch,
+        // a required field that is left out fails to decode
+        if !drift.unknown.is_empty() {
+            drifted += 1;
+        }
+        println!(
+            "{endpoint}: {} unknown, {} missing",
+            drift.unknown.len(),
+            drift.missing.len()
+        );
+        for field in &drift.unknown {
+            println!("  unknown {field}");
+        }
+        for field in &drift.missing {
+            println!("  missing {field}");
+        }
+    }
+    for (endpoint, e) in &failures.0 {
+        // a response that no longer decodes is drift too
+        if matches!(e, NaApiError::Deserialize { .. }) {
+            drifted += 1;
+        }
+        println!("{endpoint}: not checked, {e}");
+    }
+    if drifted > 0 {
+        bail!("{drifted} endpoint(s) do not match their structs");
+    }
+    println!("all checked endpoints match their structs");
+    Ok(())
+}